@@ -0,0 +1,431 @@
+//! Busca de vários padrões ao mesmo tempo com o algoritmo Aho-Corasick.
+//!
+//! O Aho-Corasick é a generalização do KMP para um conjunto de padrões: os padrões são
+//! organizados em uma trie, e cada nó ganha um "link de falha" apontando para o nó do
+//! maior sufixo próprio que também é um prefixo de algum padrão, exatamente o que a
+//! tabela LPS faz para um único padrão. Assim, o texto é percorrido uma única vez,
+//! qualquer que seja o número de padrões.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+/// Como as ocorrências são reportadas quando padrões se sobrepõem no texto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKind {
+    /// Todas as ocorrências de todos os padrões, inclusive sobrepostas, em ordem de
+    /// término no texto (e, para o mesmo término, da mais longa para a mais curta).
+    #[default]
+    Overlapping,
+    /// Ocorrências sem sobreposição, da esquerda para a direita. Entre as que começam na
+    /// posição mais à esquerda, vence o padrão que aparece primeiro na lista (a mesma
+    /// semântica de uma alternação `a|ab` em expressões regulares).
+    LeftmostFirst,
+    /// Ocorrências sem sobreposição, da esquerda para a direita. Entre as que começam na
+    /// posição mais à esquerda, vence a mais longa (empates vão para o primeiro padrão).
+    LeftmostLongest,
+}
+
+/// Um nó da trie: as transições para os filhos, o link de falha e os padrões que terminam
+/// exatamente aqui.
+#[derive(Debug, Clone)]
+struct Node<T> {
+    children: HashMap<T, usize>,
+    fail: usize,
+    depth: usize, // comprimento do prefixo representado pelo nó
+    outputs: Vec<usize>,
+    // Próximo nó na cadeia de falhas que tem alguma saída, para reportar os padrões que
+    // são sufixos deste sem percorrer a cadeia inteira.
+    output_link: Option<usize>,
+}
+
+impl<T> Node<T> {
+    fn new(depth: usize) -> Self {
+        Node {
+            children: HashMap::new(),
+            fail: ROOT,
+            depth,
+            outputs: Vec::new(),
+            output_link: None,
+        }
+    }
+}
+
+const ROOT: usize = 0;
+
+/// Um autômato Aho-Corasick pré-compilado para um conjunto de padrões.
+///
+/// Os padrões são identificados pela sua posição na lista passada ao construtor. Padrões
+/// vazios são aceitos, mas nunca produzem ocorrências (como em `kmp_search`).
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<T> {
+    nodes: Vec<Node<T>>,
+    pattern_lens: Vec<usize>,
+    match_kind: MatchKind,
+}
+
+impl<T> AhoCorasick<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Compila os padrões para reportar todas as ocorrências ([`MatchKind::Overlapping`]).
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[T]>,
+    {
+        Self::with_match_kind(patterns, MatchKind::Overlapping)
+    }
+
+    /// Compila os padrões com a semântica de reporte escolhida.
+    pub fn with_match_kind<I, P>(patterns: I, match_kind: MatchKind) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[T]>,
+    {
+        let mut nodes = vec![Node::new(0)];
+        let mut pattern_lens = Vec::new();
+
+        // 1. Inserir cada padrão na trie.
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            pattern_lens.push(pattern.len());
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut current = ROOT;
+            for element in pattern {
+                current = match nodes[current].children.get(element) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(Node::new(nodes[current].depth + 1));
+                        let child = nodes.len() - 1;
+                        nodes[current].children.insert(element.clone(), child);
+                        child
+                    }
+                };
+            }
+            nodes[current].outputs.push(id);
+        }
+
+        // 2. Calcular os links de falha em largura (BFS), do mesmo modo que a tabela LPS:
+        // a falha de um filho é obtida seguindo as falhas do pai até achar um nó que
+        // possa ser estendido pelo mesmo elemento.
+        let mut queue: VecDeque<usize> = nodes[ROOT].children.values().copied().collect();
+        while let Some(parent) = queue.pop_front() {
+            let children: Vec<(T, usize)> = nodes[parent]
+                .children
+                .iter()
+                .map(|(element, &child)| (element.clone(), child))
+                .collect();
+
+            for (element, child) in children {
+                let mut fallback = nodes[parent].fail;
+                let fail = loop {
+                    if let Some(&next) = nodes[fallback].children.get(&element) {
+                        break next;
+                    }
+                    if fallback == ROOT {
+                        break ROOT;
+                    }
+                    fallback = nodes[fallback].fail;
+                };
+
+                nodes[child].fail = fail;
+                nodes[child].output_link = if nodes[fail].outputs.is_empty() {
+                    nodes[fail].output_link
+                } else {
+                    Some(fail)
+                };
+                queue.push_back(child);
+            }
+        }
+
+        AhoCorasick {
+            nodes,
+            pattern_lens,
+            match_kind,
+        }
+    }
+
+    /// Quantidade de padrões compilados (incluindo os vazios).
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    /// A semântica de reporte usada por [`AhoCorasick::find_all`].
+    pub fn match_kind(&self) -> MatchKind {
+        self.match_kind
+    }
+
+    /// Retorna todas as ocorrências como pares `(pattern_id, offset)`, onde `offset` é o
+    /// índice de início da ocorrência no `haystack`, segundo o [`MatchKind`] escolhido.
+    pub fn find_all(&self, haystack: &[T]) -> Vec<(usize, usize)> {
+        self.find_iter(haystack).collect()
+    }
+
+    /// Iterador preguiçoso sobre as ocorrências, com os mesmos pares e a mesma ordem de
+    /// [`AhoCorasick::find_all`], mas sem guardar nada além do estado da busca.
+    ///
+    /// Nos modos `Leftmost*`, a semântica é resolvida durante a passada: o autômato para
+    /// assim que nenhuma ocorrência ainda possível pode começar antes da melhor já vista,
+    /// e a busca seguinte recomeça logo após o fim da escolhida. O custo é
+    /// O(n + k · m), com `k` ocorrências reportadas e `m` o maior padrão.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h [T]) -> FindIter<'a, 'h, T> {
+        FindIter {
+            automaton: self,
+            haystack,
+            i: 0,
+            state: ROOT,
+            output: None,
+        }
+    }
+
+    /// Indica se algum dos padrões ocorre no `haystack`.
+    pub fn is_match(&self, haystack: &[T]) -> bool {
+        let mut state = ROOT;
+        haystack.iter().any(|element| {
+            state = self.next_state(state, element);
+            !self.nodes[state].outputs.is_empty() || self.nodes[state].output_link.is_some()
+        })
+    }
+
+    /// Segue as transições (e, quando não há transição, os links de falha) a partir de
+    /// `state` com o próximo elemento do texto.
+    fn next_state(&self, mut state: usize, element: &T) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[state].children.get(element) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.nodes[state].fail;
+        }
+    }
+
+    /// A ocorrência "leftmost" que começa a partir de `at`, segundo o [`MatchKind`].
+    ///
+    /// O nó atual representa o maior sufixo do texto lido que ainda pode virar uma
+    /// ocorrência; se ele começa depois da melhor ocorrência encontrada, nenhuma outra pode
+    /// começar antes dela (nem na mesma posição), e a busca termina.
+    fn find_leftmost_at(&self, haystack: &[T], at: usize) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        let mut state = ROOT;
+
+        for (i, element) in haystack.iter().enumerate().skip(at) {
+            state = self.next_state(state, element);
+            if let Some((_, start)) = best {
+                if i + 1 - self.nodes[state].depth > start {
+                    break;
+                }
+            }
+
+            let mut node = Some(state);
+            while let Some(current) = node {
+                for &id in &self.nodes[current].outputs {
+                    let candidate = (id, i + 1 - self.pattern_lens[id]);
+                    if best.is_none_or(|best| self.prefers(candidate, best)) {
+                        best = Some(candidate);
+                    }
+                }
+                node = self.nodes[current].output_link;
+            }
+        }
+
+        best
+    }
+
+    /// Indica se a ocorrência `a` vence `b` na semântica "leftmost": a que começa mais à
+    /// esquerda e, no empate, o primeiro padrão ou o mais longo, conforme o modo.
+    fn prefers(&self, (a_id, a_start): (usize, usize), (b_id, b_start): (usize, usize)) -> bool {
+        if a_start != b_start {
+            return a_start < b_start;
+        }
+        match self.match_kind {
+            MatchKind::LeftmostLongest if self.pattern_lens[a_id] != self.pattern_lens[b_id] => {
+                self.pattern_lens[a_id] > self.pattern_lens[b_id]
+            }
+            _ => a_id < b_id,
+        }
+    }
+}
+
+/// Iterador sobre as ocorrências de um [`AhoCorasick`], criado por
+/// [`AhoCorasick::find_iter`].
+#[derive(Debug, Clone)]
+pub struct FindIter<'a, 'h, T> {
+    automaton: &'a AhoCorasick<T>,
+    haystack: &'h [T],
+    i: usize,     // próximo elemento do haystack a ser lido
+    state: usize, // nó atual do autômato (só no modo `Overlapping`)
+    // Próxima saída a reportar no modo `Overlapping`: o nó da cadeia de saídas e o índice
+    // em `outputs`.
+    output: Option<(usize, usize)>,
+}
+
+impl<T> Iterator for FindIter<'_, '_, T>
+where
+    T: Eq + Hash + Clone,
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let automaton = self.automaton;
+        if automaton.match_kind != MatchKind::Overlapping {
+            return match automaton.find_leftmost_at(self.haystack, self.i) {
+                Some((id, start)) => {
+                    self.i = start + automaton.pattern_lens[id];
+                    Some((id, start))
+                }
+                None => {
+                    self.i = self.haystack.len();
+                    None
+                }
+            };
+        }
+
+        loop {
+            // Reporta os padrões que terminam no nó atual e, pelos links de saída, os que
+            // são sufixos dele (sempre mais curtos).
+            if let Some((node, k)) = self.output {
+                match automaton.nodes[node].outputs.get(k) {
+                    Some(&id) => {
+                        self.output = Some((node, k + 1));
+                        return Some((id, self.i - automaton.pattern_lens[id]));
+                    }
+                    None => {
+                        self.output = automaton.nodes[node].output_link.map(|next| (next, 0));
+                        continue;
+                    }
+                }
+            }
+
+            let element = self.haystack.get(self.i)?;
+            self.state = automaton.next_state(self.state, element);
+            self.i += 1;
+            self.output = Some((self.state, 0));
+        }
+    }
+}
+
+impl<T> FusedIterator for FindIter<'_, '_, T> where T: Eq + Hash + Clone {}
+
+#[cfg(test)]
+mod tests {
+    use super::{AhoCorasick, MatchKind};
+
+    /// Gerador xorshift, para ter entradas "aleatórias" reprodutíveis sem dependências.
+    fn next(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    fn find_all(patterns: &[&str], match_kind: MatchKind, haystack: &str) -> Vec<(usize, usize)> {
+        let patterns = patterns.iter().map(|pattern| pattern.as_bytes());
+        AhoCorasick::with_match_kind(patterns, match_kind).find_all(haystack.as_bytes())
+    }
+
+    /// A semântica "leftmost" pela definição: a cada passo, testa todas as posições de
+    /// início, da esquerda para a direita, e todos os padrões em cada uma.
+    fn naive_leftmost(
+        patterns: &[Vec<u8>],
+        match_kind: MatchKind,
+        haystack: &[u8],
+    ) -> Vec<(usize, usize)> {
+        let mut results = Vec::new();
+        let mut at = 0;
+        while at < haystack.len() {
+            let candidates = (0..patterns.len()).filter(|&id| {
+                !patterns[id].is_empty() && haystack[at..].starts_with(&patterns[id])
+            });
+            let best = match match_kind {
+                MatchKind::LeftmostLongest => {
+                    // `min_by_key` fica com o primeiro entre os de mesmo comprimento.
+                    candidates.min_by_key(|&id| usize::MAX - patterns[id].len())
+                }
+                _ => candidates.min(),
+            };
+            match best {
+                Some(id) => {
+                    results.push((id, at));
+                    at += patterns[id].len();
+                }
+                None => at += 1,
+            }
+        }
+        results
+    }
+
+    #[test]
+    fn leftmost_first_follows_pattern_order() {
+        let kind = MatchKind::LeftmostFirst;
+        assert_eq!(find_all(&["a", "ab"], kind, "abab"), [(0, 0), (0, 2)]);
+        assert_eq!(find_all(&["ab", "a"], kind, "abab"), [(0, 0), (0, 2)]);
+        assert_eq!(find_all(&["ab", "a"], kind, "aab"), [(1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn leftmost_longest_prefers_longer_match() {
+        let kind = MatchKind::LeftmostLongest;
+        assert_eq!(find_all(&["a", "ab"], kind, "abab"), [(1, 0), (1, 2)]);
+        assert_eq!(find_all(&["ab", "a"], kind, "abab"), [(0, 0), (0, 2)]);
+        assert_eq!(find_all(&["a", "abc", "ab"], kind, "abx"), [(2, 0)]);
+    }
+
+    #[test]
+    fn leftmost_ties_at_same_start() {
+        // Padrões iguais: em ambos os modos, vence o primeiro.
+        for kind in [MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+            assert_eq!(find_all(&["ab", "ab"], kind, "xab"), [(0, 1)]);
+        }
+        // Uma ocorrência que termina antes não vence uma que começa antes e ainda está
+        // sendo lida: a busca só para quando o início do estado atual passa do melhor.
+        for kind in [MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+            assert_eq!(find_all(&["b", "abc"], kind, "abc"), [(1, 0)]);
+            assert_eq!(find_all(&["abcd", "bc"], kind, "abcx"), [(1, 1)]);
+            assert_eq!(find_all(&["bcd", "abcx"], kind, "abcd"), [(0, 1)]);
+        }
+    }
+
+    #[test]
+    fn empty_patterns_never_match() {
+        for kind in [
+            MatchKind::Overlapping,
+            MatchKind::LeftmostFirst,
+            MatchKind::LeftmostLongest,
+        ] {
+            assert_eq!(find_all(&[""], kind, "abc"), []);
+            assert_eq!(find_all(&["", "b"], kind, "abc"), [(1, 1)]);
+            assert_eq!(find_all(&["b", ""], kind, ""), []);
+        }
+        let automaton = AhoCorasick::new([b"".as_slice()]);
+        assert!(!automaton.is_match(b"abc".as_slice()));
+        assert_eq!(automaton.pattern_count(), 1);
+    }
+
+    #[test]
+    fn leftmost_matches_naive_on_random_input() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..3000 {
+            let mut pick = |max_len| {
+                let len = (next(&mut seed) % max_len) as usize;
+                Vec::from_iter((0..len).map(|_| b'a' + next(&mut seed) as u8 % 3))
+            };
+            let haystack = pick(30);
+            let patterns = Vec::from_iter((0..4).map(|_| pick(5)));
+
+            for kind in [MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+                let automaton = AhoCorasick::with_match_kind(&patterns, kind);
+                assert_eq!(
+                    automaton.find_all(&haystack),
+                    naive_leftmost(&patterns, kind, &haystack),
+                    "{kind:?}, patterns {patterns:?}, haystack {haystack:?}"
+                );
+            }
+        }
+    }
+}
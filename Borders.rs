@@ -0,0 +1,83 @@
+//! Análise de bordas de uma sequência, a partir da tabela LPS (função de prefixo).
+//!
+//! Uma borda é um trecho que é, ao mesmo tempo, prefixo próprio e sufixo da sequência.
+//! A tabela LPS guarda a maior borda de cada prefixo e, seguindo-a em cadeia
+//! (`lps[n - 1]`, `lps[lps[n - 1] - 1]`, ...), obtemos todas as outras.
+
+use crate::compute_lps_table;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Retorna os comprimentos de todas as bordas de `s`, da maior para a menor.
+///
+/// Por exemplo, as bordas de `"abacaba"` são `"aba"` e `"a"`, então o resultado é `[3, 1]`.
+/// A borda vazia não é incluída.
+pub fn all_borders<T>(s: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let lps = compute_lps_table(s);
+    let mut borders = Vec::new();
+
+    let mut border = lps.last().copied().unwrap_or(0);
+    while border > 0 {
+        borders.push(border);
+        border = lps[border - 1];
+    }
+
+    borders
+}
+
+/// Retorna o menor período de `s`: o menor `p > 0` tal que `s[i] == s[i + p]` para todo
+/// `i` válido. É sempre `s.len()` menos a maior borda. Para uma sequência vazia, retorna 0.
+pub fn smallest_period<T>(s: &[T]) -> usize
+where
+    T: PartialEq,
+{
+    let lps = compute_lps_table(s);
+    s.len() - lps.last().copied().unwrap_or(0)
+}
+
+/// Indica se `s` é uma potência exata de um bloco mais curto, isto é, se `s` é o mesmo
+/// bloco repetido duas ou mais vezes (como `"abcabc"`, mas não `"abcab"` nem `"abc"`).
+///
+/// Isso acontece exatamente quando o menor período é menor que `s.len()` e o divide.
+pub fn is_power<T>(s: &[T]) -> bool
+where
+    T: PartialEq,
+{
+    let period = smallest_period(s);
+    period < s.len() && s.len().is_multiple_of(period)
+}
+
+/// Conta quantas vezes cada prefixo de `s` ocorre em `s` (inclusive sobrepostas).
+///
+/// O resultado tem `s.len() + 1` posições: `counts[k]` é o número de ocorrências do prefixo
+/// de comprimento `k`. Por convenção, o prefixo vazio ocorre `s.len() + 1` vezes (uma em
+/// cada posição, inclusive no fim), e `counts[s.len()]` é sempre 1.
+pub fn prefix_occurrence_counts<T>(s: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let n = s.len();
+    let lps = compute_lps_table(s);
+    let mut counts = vec![0; n + 1];
+
+    // Cada posição `i` é o fim de uma ocorrência da sua maior borda `lps[i]`...
+    for &border in &lps {
+        counts[border] += 1;
+    }
+    // ...e também das bordas dessa borda. Propagamos as contagens dos prefixos mais longos
+    // para as suas maiores bordas, do maior para o menor.
+    for length in (1..=n).rev() {
+        let border = lps[length - 1];
+        counts[border] += counts[length];
+    }
+    // Por fim, cada prefixo ocorre também na sua própria posição, no início de `s`.
+    for count in counts.iter_mut().skip(1) {
+        *count += 1;
+    }
+    counts[0] = n + 1;
+
+    counts
+}
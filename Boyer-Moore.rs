@@ -0,0 +1,329 @@
+//! Os algoritmos Boyer-Moore e Boyer-Moore-Horspool.
+//!
+//! Ao contrário do KMP, que examina todos os elementos do texto, estes algoritmos comparam
+//! o padrão da direita para a esquerda e, a cada divergência, saltam por cima de trechos
+//! do texto que não podem conter uma ocorrência. Com padrões longos sobre alfabetos
+//! grandes, a maior parte do texto nem chega a ser lida.
+//!
+//! As funções seguem o mesmo contrato de `kmp_search`: retornam os índices de início de
+//! todas as ocorrências, inclusive sobrepostas, em ordem crescente, e um padrão vazio (ou
+//! maior que o texto) não tem ocorrências. As versões genéricas usam uma tabela de
+//! "caractere ruim" baseada em `HashMap`, qualquer que seja o tipo; as versões `_bytes`
+//! usam uma tabela de 256 posições, indexada diretamente pelo byte, e são as que devem
+//! ser usadas com `&[u8]`. As versões genéricas precisam da feature `std`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Encontra todas as ocorrências de `needle` em `haystack` com o algoritmo Boyer-Moore,
+/// usando as regras do caractere ruim e do sufixo bom.
+///
+/// A tabela do caractere ruim é sempre um `HashMap`, mesmo quando `T` é `u8`: para bytes,
+/// use [`bm_search_bytes`], que dá os mesmos resultados com uma tabela indexada
+/// diretamente pelo byte, sem hashing a cada salto.
+#[cfg(feature = "std")]
+pub fn bm_search<T>(haystack: &[T], needle: &[T]) -> Vec<usize>
+where
+    T: Eq + Hash,
+{
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return vec![];
+    }
+    boyer_moore(haystack, needle, &HashTable::new(needle))
+}
+
+/// Como `bm_search`, mas especializado para bytes (e disponível sem a feature `std`).
+pub fn bm_search_bytes(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return vec![];
+    }
+    boyer_moore(haystack, needle, &ByteTable::new(needle))
+}
+
+/// Versão preguiçosa de [`bm_search_bytes`]: as ocorrências são produzidas uma a uma, em
+/// ordem crescente, e a busca avança só o necessário para encontrar a próxima.
+pub fn bm_find_iter_bytes<'h, 'n>(haystack: &'h [u8], needle: &'n [u8]) -> BmFindIterBytes<'h, 'n> {
+    // Casos base: sem ocorrências possíveis, nem vale a pena calcular as tabelas.
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return BmFindIterBytes {
+            haystack: &[],
+            needle,
+            bad_char: None,
+            good_suffix: Vec::new(),
+            pos: 0,
+        };
+    }
+
+    BmFindIterBytes {
+        haystack,
+        needle,
+        bad_char: Some(ByteTable::new(needle)),
+        good_suffix: compute_good_suffix_table(needle),
+        pos: 0,
+    }
+}
+
+/// Iterador sobre as ocorrências de um padrão de bytes, criado por [`bm_find_iter_bytes`].
+#[derive(Debug, Clone)]
+pub struct BmFindIterBytes<'h, 'n> {
+    haystack: &'h [u8],
+    needle: &'n [u8],
+    bad_char: Option<ByteTable>, // `None` quando não há ocorrências possíveis
+    good_suffix: Vec<usize>,
+    pos: usize, // próximo alinhamento do padrão no texto
+}
+
+impl Iterator for BmFindIterBytes<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let bad_char = self.bad_char.as_ref()?;
+        next_bm_match(
+            self.haystack,
+            self.needle,
+            bad_char,
+            &self.good_suffix,
+            &mut self.pos,
+        )
+    }
+}
+
+impl FusedIterator for BmFindIterBytes<'_, '_> {}
+
+/// Encontra todas as ocorrências de `needle` em `haystack` com o algoritmo
+/// Boyer-Moore-Horspool, que usa apenas a regra do caractere ruim, aplicada sempre ao
+/// elemento do texto alinhado com o fim do padrão.
+///
+/// Como em [`bm_search`], a tabela é sempre um `HashMap`: para bytes, use
+/// [`horspool_search_bytes`].
+#[cfg(feature = "std")]
+pub fn horspool_search<T>(haystack: &[T], needle: &[T]) -> Vec<usize>
+where
+    T: Eq + Hash,
+{
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return vec![];
+    }
+    horspool(haystack, needle, &HashTable::new(needle))
+}
+
+/// Como `horspool_search`, mas especializado para bytes (e disponível sem a feature `std`).
+pub fn horspool_search_bytes(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return vec![];
+    }
+    horspool(haystack, needle, &ByteTable::new(needle))
+}
+
+/// Tabela do "caractere ruim": para cada elemento, a distância entre a sua última
+/// ocorrência em `needle[..m - 1]` e o fim do padrão (ou `m`, se ele não aparece).
+trait BadCharTable<T> {
+    fn shift(&self, element: &T) -> usize;
+}
+
+/// Tabela de caractere ruim para bytes: um array indexado pelo próprio byte.
+#[derive(Debug, Clone)]
+struct ByteTable {
+    shifts: [usize; 256],
+}
+
+impl ByteTable {
+    fn new(needle: &[u8]) -> Self {
+        let m = needle.len();
+        let mut shifts = [m; 256];
+        for (i, &byte) in needle[..m - 1].iter().enumerate() {
+            shifts[byte as usize] = m - 1 - i;
+        }
+        ByteTable { shifts }
+    }
+}
+
+impl BadCharTable<u8> for ByteTable {
+    fn shift(&self, element: &u8) -> usize {
+        self.shifts[*element as usize]
+    }
+}
+
+/// Tabela de caractere ruim para um tipo qualquer: só os elementos do padrão são
+/// guardados; todos os outros saltam o padrão inteiro.
+#[cfg(feature = "std")]
+struct HashTable<'n, T> {
+    shifts: HashMap<&'n T, usize>,
+    default: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'n, T> HashTable<'n, T>
+where
+    T: Eq + Hash,
+{
+    fn new(needle: &'n [T]) -> Self {
+        let m = needle.len();
+        let mut shifts = HashMap::with_capacity(m);
+        for (i, element) in needle[..m - 1].iter().enumerate() {
+            shifts.insert(element, m - 1 - i);
+        }
+        HashTable { shifts, default: m }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> BadCharTable<T> for HashTable<'_, T>
+where
+    T: Eq + Hash,
+{
+    fn shift(&self, element: &T) -> usize {
+        self.shifts.get(element).copied().unwrap_or(self.default)
+    }
+}
+
+/// Laço principal do Boyer-Moore. `needle` não pode ser vazio nem maior que `haystack`.
+fn boyer_moore<T, B>(haystack: &[T], needle: &[T], bad_char: &B) -> Vec<usize>
+where
+    T: PartialEq,
+    B: BadCharTable<T>,
+{
+    let good_suffix = compute_good_suffix_table(needle);
+    let mut results = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = next_bm_match(haystack, needle, bad_char, &good_suffix, &mut pos) {
+        results.push(start);
+    }
+    results
+}
+
+/// Um passo do laço do Boyer-Moore: procura a próxima ocorrência a partir do alinhamento
+/// `*pos` e deixa `*pos` pronto para a busca seguinte. `needle` não pode ser vazio nem
+/// maior que `haystack`.
+fn next_bm_match<T, B>(
+    haystack: &[T],
+    needle: &[T],
+    bad_char: &B,
+    good_suffix: &[usize],
+    pos: &mut usize,
+) -> Option<usize>
+where
+    T: PartialEq,
+    B: BadCharTable<T>,
+{
+    let (n, m) = (haystack.len(), needle.len());
+    while *pos <= n - m {
+        let start = *pos;
+        // Compara da direita para a esquerda. `i` é o comprimento do trecho ainda não
+        // comparado, ou seja, a divergência (se houver) está em `needle[i - 1]`.
+        let mut i = m;
+        while i > 0 && needle[i - 1] == haystack[start + i - 1] {
+            i -= 1;
+        }
+
+        if i == 0 {
+            // Salta para o próximo alinhamento compatível com o período do padrão, o que
+            // preserva as ocorrências sobrepostas.
+            *pos += good_suffix[0];
+            return Some(start);
+        }
+
+        // Divergência em `needle[j]`. Regra do caractere ruim: alinha a última ocorrência
+        // do elemento do texto no padrão com a divergência (o salto pode ser negativo,
+        // daí o `saturating_sub`); a regra do sufixo bom garante um salto positivo.
+        let j = i - 1;
+        let bad_char_shift = bad_char
+            .shift(&haystack[start + j])
+            .saturating_sub(m - 1 - j);
+        *pos += good_suffix[j].max(bad_char_shift);
+    }
+
+    None
+}
+
+/// Laço principal do Horspool. `needle` não pode ser vazio nem maior que `haystack`.
+fn horspool<T, B>(haystack: &[T], needle: &[T], bad_char: &B) -> Vec<usize>
+where
+    T: PartialEq,
+    B: BadCharTable<T>,
+{
+    let (n, m) = (haystack.len(), needle.len());
+    let mut results = Vec::new();
+
+    let mut pos = 0;
+    while pos <= n - m {
+        let last = &haystack[pos + m - 1];
+        if *last == needle[m - 1] && needle[..m - 1] == haystack[pos..pos + m - 1] {
+            results.push(pos);
+        }
+        // O salto depende só do elemento alinhado com o fim do padrão, e nunca é maior do
+        // que a distância até a sua próxima ocorrência possível, então nenhuma ocorrência
+        // (nem as sobrepostas) é pulada.
+        pos += bad_char.shift(last);
+    }
+
+    results
+}
+
+/// Calcula a tabela do "sufixo bom": `table[j]` é o quanto o padrão pode avançar quando a
+/// divergência acontece em `needle[j]`, depois de `needle[j + 1..]` já ter casado.
+/// `table[0]` também é o salto usado após uma ocorrência completa (o período do padrão).
+fn compute_good_suffix_table<T>(needle: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let m = needle.len();
+    let suffixes = compute_suffixes(needle);
+    let mut table = vec![m; m];
+
+    // Caso 1: só um prefixo do padrão casa com um sufixo do trecho já comparado.
+    let mut j = 0;
+    for i in (0..m).rev() {
+        if suffixes[i] == i + 1 {
+            while j < m - 1 - i {
+                if table[j] == m {
+                    table[j] = m - 1 - i;
+                }
+                j += 1;
+            }
+        }
+    }
+
+    // Caso 2: o trecho já comparado reaparece inteiro em outra posição do padrão.
+    for i in 0..m.saturating_sub(1) {
+        table[m - 1 - suffixes[i]] = m - 1 - i;
+    }
+
+    table
+}
+
+/// `suffixes[i]` é o comprimento do maior sufixo de `needle[..=i]` que também é sufixo do
+/// padrão inteiro.
+fn compute_suffixes<T>(needle: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let m = needle.len();
+    let mut suffixes = vec![0; m];
+    suffixes[m - 1] = m;
+
+    // `[g + 1, f]` é a janela mais à esquerda já conhecida que casa com um sufixo do padrão;
+    // dentro dela, os valores podem ser reaproveitados, como no algoritmo Z.
+    let mut g = m as isize - 1;
+    let mut f = m as isize - 1;
+    for i in (0..m as isize - 1).rev() {
+        let mirrored = (i + m as isize - 1 - f) as usize;
+        if i > g && (suffixes[mirrored] as isize) < i - g {
+            suffixes[i as usize] = suffixes[mirrored];
+        } else {
+            g = g.min(i);
+            f = i;
+            while g >= 0 && needle[g as usize] == needle[(g + m as isize - 1 - f) as usize] {
+                g -= 1;
+            }
+            suffixes[i as usize] = (f - g) as usize;
+        }
+    }
+
+    suffixes
+}
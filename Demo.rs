@@ -0,0 +1,242 @@
+//! Os exemplos de uso da biblioteca, executados pelo subcomando `hofalgs demo`.
+
+use hofalgs::*;
+use std::io::{self, Write};
+
+/// Executa os exemplos, escrevendo na saída padrão. Um erro de escrita (por exemplo, a
+/// saída fechada por `hofalgs demo | head`) interrompe os exemplos e é retornado.
+pub fn run() -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    // Exemplo 1: Busca de texto (string)
+    let text = "ABABCABABABCD";
+    let pattern = "ABABCD";
+
+    // Convertendo para vetores de char para usar a função genérica
+    // (veja o Exemplo 9 para a busca direta em `&str`)
+    let text_chars: Vec<char> = text.chars().collect();
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+
+    let matches = kmp_search(&text_chars, &pattern_chars);
+    writeln!(out, "Texto: '{}'", text)?;
+    writeln!(out, "Padrão: '{}'", pattern)?;
+    writeln!(out, "Padrão encontrado nos índices: {:?}", matches)?; // Deve imprimir [7]
+    let lowercase: Vec<char> = "ababcd".chars().collect();
+    let ignoring_case = kmp_search_by(&text_chars, &lowercase, |a, b| a.eq_ignore_ascii_case(b));
+    writeln!(out, "Ignorando maiúsculas/minúsculas: {:?}", ignoring_case)?; // Deve imprimir [7]
+    writeln!(out, "---")?;
+
+    // Exemplo 2: Múltiplas ocorrências, incluindo sobrepostas
+    let text2 = "abababa";
+    let pattern2 = "aba";
+    let text_chars2: Vec<char> = text2.chars().collect();
+    let pattern_chars2: Vec<char> = pattern2.chars().collect();
+
+    let matches2 = kmp_search(&text_chars2, &pattern_chars2);
+    writeln!(out, "Texto: '{}'", text2)?;
+    writeln!(out, "Padrão: '{}'", pattern2)?;
+    writeln!(out, "Padrão encontrado nos índices: {:?}", matches2)?; // Deve imprimir [0, 2, 4]
+    let first_two: Vec<usize> = find_iter(&text_chars2, &pattern_chars2).take(2).collect();
+    writeln!(out, "Apenas as duas primeiras: {:?}", first_two)?; // Deve imprimir [0, 2]
+    let disjoint = kmp_search_non_overlapping(&text_chars2, &pattern_chars2);
+    writeln!(out, "Sem sobreposição: {:?}", disjoint)?; // Deve imprimir [0, 4]
+    let last = rfind(&text_chars2, &pattern_chars2);
+    writeln!(out, "Última ocorrência: {:?}", last)?; // Deve imprimir Some(4)
+    writeln!(out, "---")?;
+
+    // Exemplo 3: Genérico, usando números (u8)
+    let sequence: Vec<u8> = vec![1, 2, 3, 1, 2, 4, 5, 1, 2, 3, 1, 2, 3, 5];
+    let sub_sequence: Vec<u8> = vec![1, 2, 3, 5];
+
+    let matches3 = kmp_search(&sequence, &sub_sequence);
+    writeln!(out, "Sequência: {:?}", sequence)?;
+    writeln!(out, "Sub-sequência: {:?}", sub_sequence)?;
+    writeln!(out, "Sub-sequência encontrada nos índices: {:?}", matches3)?; // Deve imprimir [9]
+    let events = [("login", 10), ("click", 12), ("login", 20), ("logout", 21)];
+    let sessions = kmp_search_by_key(&events, &["login", "logout"], |&(kind, _)| kind);
+    writeln!(out, "Sessões login->logout nos eventos: {:?}", sessions)?; // Deve imprimir [2]
+    writeln!(out, "---")?;
+
+    // Exemplo 4: Sem ocorrências
+    let text4 = "abcdefg";
+    let pattern4 = "xyz";
+    let text_chars4: Vec<char> = text4.chars().collect();
+    let pattern_chars4: Vec<char> = pattern4.chars().collect();
+
+    let matches4 = kmp_search(&text_chars4, &pattern_chars4);
+    writeln!(out, "Texto: '{}'", text4)?;
+    writeln!(out, "Padrão: '{}'", pattern4)?;
+    writeln!(out, "Padrão encontrado nos índices: {:?}", matches4)?; // Deve imprimir []
+    writeln!(out, "---")?;
+
+    // Exemplo 5: Padrão pré-compilado, reutilizado em vários textos
+    let records = ["GET /index.html", "POST /login", "GET /login?next=/"];
+    let matcher = Kmp::new(b"login");
+    for record in records {
+        writeln!(
+            out,
+            "Registro: '{}' -> primeira ocorrência: {:?}, total: {}",
+            record,
+            matcher.find_first(record.as_bytes()),
+            matcher.count(record.as_bytes())
+        )?;
+    }
+    writeln!(out, "---")?;
+
+    // Exemplo 6: Busca em streaming, com o padrão atravessando a fronteira entre pedaços
+    let mut stream = KmpStream::new(b"ABABCD");
+    let chunks: [&[u8]; 3] = [b"ABABCAB", b"ABA", b"BCD"];
+    for chunk in chunks {
+        let found = stream.feed(chunk);
+        let chunk = String::from_utf8_lossy(chunk);
+        writeln!(out, "Pedaço: {:?} -> {:?}", chunk, found)?; // [7] no último
+    }
+    writeln!(out, "---")?;
+
+    // Exemplo 7: Busca direto em um `std::io::Read`, sem carregar tudo em memória
+    let log = "INFO start\nERROR disk full\nINFO retry\nERROR disk full\n";
+    let offsets: io::Result<Vec<u64>> = search_reader(log.as_bytes(), b"ERROR").collect();
+    writeln!(out, "Offsets de 'ERROR' no log: {:?}", offsets)?; // Deve imprimir Ok([11, 38])
+
+    // O mesmo, direto de um arquivo (mapeado em memória, com a feature `mmap`)
+    let log_path = std::env::temp_dir().join(format!("hofalgs-{}.log", std::process::id()));
+    if std::fs::write(&log_path, log).is_ok() {
+        writeln!(
+            out,
+            "Offsets no arquivo: {:?}",
+            search_file(&log_path, b"ERROR")
+        )?; // Ok([11, 38])
+        let _ = std::fs::remove_file(&log_path);
+    }
+    writeln!(out, "---")?;
+
+    // Exemplo 8: Tipos diferentes no texto e no padrão (`String` contra `&str`)
+    let tokens: Vec<String> = "let x = y + 1 ; let z = x + 1 ;"
+        .split(' ')
+        .map(String::from)
+        .collect();
+    let matches_tokens = kmp_search(&tokens, &["+", "1", ";"]);
+    writeln!(out, "Tokens: {:?}", tokens)?;
+    writeln!(out, "Sequência [+, 1, ;] nos índices: {:?}", matches_tokens)?; // Deve imprimir [4, 11]
+    writeln!(out, "---")?;
+
+    // Exemplo 9: Busca direta em `&str`, com faixas de bytes que podem fatiar o texto
+    let text9 = "maçã, pêra e maçã-verde";
+    let ranges = kmp_find_str(text9, "maçã");
+    let slices: Vec<&str> = ranges.iter().map(|r| &text9[r.clone()]).collect();
+    writeln!(out, "Texto: '{}'", text9)?;
+    writeln!(out, "Faixas de bytes: {:?} -> {:?}", ranges, slices)?; // [0..6, 16..22]
+    let char_ranges = byte_ranges_to_char_ranges(text9, &ranges);
+    writeln!(out, "Faixas de chars: {:?}", char_ranges)?; // [0..4, 13..17]
+    writeln!(out, "---")?;
+
+    // Exemplo 10: Vários padrões de uma só vez (Aho-Corasick)
+    let text10 = "ushers";
+    let keywords = ["he", "she", "his", "hers"];
+    let automaton = aho_corasick::AhoCorasick::new(keywords.iter().map(|k| k.as_bytes()));
+    let found10 = automaton.find_all(text10.as_bytes());
+    writeln!(out, "Texto: '{}', padrões: {:?}", text10, keywords)?;
+    writeln!(out, "(padrão, índice): {:?}", found10)?; // Deve imprimir [(1, 1), (0, 2), (3, 2)]
+    writeln!(out, "---")?;
+
+    // Exemplo 11: Outros algoritmos, com o mesmo resultado do KMP
+    let text11 = b"here is a simple example, with an example of a simple example";
+    let pattern11 = b"example";
+    writeln!(out, "KMP:      {:?}", kmp_search(text11, pattern11))?;
+    writeln!(out, "KMP SIMD: {:?}", kmp_search_bytes(text11, pattern11))?;
+    #[cfg(feature = "rayon")]
+    writeln!(out, "KMP par.: {:?}", par_kmp_search(text11, pattern11))?;
+    writeln!(
+        out,
+        "BM:       {:?}",
+        boyer_moore::bm_search_bytes(text11, pattern11)
+    )?;
+    writeln!(
+        out,
+        "Horspool: {:?}",
+        boyer_moore::horspool_search_bytes(text11, pattern11)
+    )?;
+    writeln!(
+        out,
+        "Two-Way:  {:?}",
+        two_way::two_way_search(text11, pattern11)
+    )?;
+    writeln!(
+        out,
+        "Z:        {:?}",
+        z_algorithm::z_search(text11, pattern11)
+    )?;
+    writeln!(
+        out,
+        "KMP DFA:  {:?}",
+        kmp_dfa::KmpDfa::new(pattern11).find_all(text11)
+    )?; // [17, 34, 54]
+    writeln!(out, "---")?;
+
+    // Exemplo 12: Bordas e períodos a partir da tabela LPS
+    let block: Vec<char> = "abcabcabc".chars().collect();
+    writeln!(
+        out,
+        "Tabela LPS de 'abcabcabc': {:?}",
+        compute_lps_table(&block)
+    )?;
+    writeln!(out, "Bordas: {:?}", borders::all_borders(&block))?; // [6, 3]
+    writeln!(out, "Menor período: {}", borders::smallest_period(&block))?; // 3
+    writeln!(out, "É uma potência exata? {}", borders::is_power(&block))?; // true
+    writeln!(out, "---")?;
+
+    // Exemplo 13: Busca em tempo real (um acesso à tabela por byte), pela mesma interface
+    let mut searchers: Vec<Box<dyn StreamSearcher<u8>>> = vec![
+        Box::new(KmpStream::new(b"aab")),
+        Box::new(kmp_dfa::DfaStream::new(b"aab")),
+    ];
+    for searcher in &mut searchers {
+        let mut found = searcher.feed(b"aaaa");
+        found.extend(searcher.feed(b"baab"));
+        writeln!(out, "Ocorrências de 'aab': {:?}", found)?; // [2, 5]
+    }
+    writeln!(out, "---")?;
+
+    // Exemplo 14: Tabela de falhas forte e contagem de comparações
+    let haystack = b"aaabaaabaaabaaaa";
+    for failure_function in [FailureFunction::Lps, FailureFunction::Strong] {
+        let kmp = Kmp::with_failure_function(b"aaaa", failure_function);
+        let mut matches = kmp.find_iter(haystack);
+        let found: Vec<usize> = matches.by_ref().collect();
+        writeln!(
+            out,
+            "{:?}: ocorrências {:?}, {} comparações",
+            failure_function,
+            found,
+            matches.comparisons()
+        )?;
+    }
+    writeln!(out, "---")?;
+
+    // Exemplo 15: Busca sem alocação (a mesma API disponível em `no_std`)
+    let needle = b"aba";
+    let mut buffer = [0; 16];
+    let lps = compute_lps_into(needle, &mut buffer);
+    let mut found = [0; 8];
+    let mut count = 0;
+    kmp_search_with(b"abababa", needle, lps, |start| {
+        found[count] = start;
+        count += 1;
+    });
+    writeln!(
+        out,
+        "Ocorrências de 'aba' sem alocar: {:?}",
+        &found[..count]
+    )?; // [0, 2, 4]
+    writeln!(out, "---")?;
+
+    // Exemplo 16: Busca incremental de capacidade fixa, com a tabela calculada na compilação
+    const FRAME_START: static_kmp::StaticKmp<u8, 4> = static_kmp::StaticKmp::new(b"\x7e\x7e");
+    let mut matcher = FRAME_START;
+    let mut found = matcher.feed(b"ab\x7e");
+    found.extend(matcher.feed(b"\x7e\x7ecd"));
+    writeln!(out, "Ocorrências de 0x7e 0x7e: {:?}", found)?; // [2, 3]
+
+    Ok(())
+}
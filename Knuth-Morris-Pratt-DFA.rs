@@ -0,0 +1,255 @@
+//! O KMP como um autômato finito determinístico (DFA) sobre bytes.
+//!
+//! No laço do KMP, um único elemento do texto pode seguir vários links da tabela LPS até
+//! achar um prefixo que continue casando. Para um alfabeto pequeno como o dos bytes, dá
+//! para pagar esse custo uma vez só: calculamos de antemão, para cada estado (quantos
+//! elementos do padrão já casaram) e cada byte possível, qual é o próximo estado. Na
+//! busca, cada byte do texto custa exatamente uma consulta à tabela.
+//!
+//! Para manter a tabela pequena, os bytes são agrupados em classes: cada byte que aparece
+//! no padrão tem a sua própria classe, e todos os outros (que sempre levam pelo mesmo
+//! caminho) dividem uma única classe. A tabela tem `(m + 1) × classes` entradas, em vez de
+//! `(m + 1) × 256`.
+
+use crate::{compute_lps_table, StreamSearcher};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Um padrão de bytes compilado em um DFA do KMP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmpDfa {
+    needle_len: usize,
+    byte_classes: [u8; 256],
+    class_count: usize,
+    transitions: Vec<usize>, // linha `state * class_count`, coluna = classe do byte
+}
+
+impl KmpDfa {
+    /// Compila o padrão, calculando a tabela LPS e, a partir dela, todas as transições.
+    pub fn new(needle: &[u8]) -> Self {
+        let m = needle.len();
+
+        // 1. Classes de bytes: uma para cada byte distinto do padrão, na ordem em que
+        // aparecem, e uma última compartilhada por todos os bytes que não aparecem.
+        let mut seen = [false; 256];
+        let mut distinct = Vec::new();
+        for &byte in needle {
+            if !seen[byte as usize] {
+                seen[byte as usize] = true;
+                distinct.push(byte);
+            }
+        }
+        // Se todos os 256 bytes aparecem no padrão, a classe compartilhada não é usada.
+        let class_count = (distinct.len() + 1).min(256);
+        let mut byte_classes = [distinct.len().min(255) as u8; 256];
+        for (class, &byte) in distinct.iter().enumerate() {
+            byte_classes[byte as usize] = class as u8;
+        }
+
+        // 2. Transições. No estado `s`, o byte do padrão `needle[s]` avança para `s + 1`;
+        // qualquer outro byte se comporta como no estado `lps[s - 1]`, cuja linha já foi
+        // calculada. É o laço do KMP, só que executado para todos os bytes de antemão.
+        let lps_table = compute_lps_table(needle);
+        let mut transitions = vec![0; (m + 1) * class_count];
+        if m > 0 {
+            transitions[byte_classes[needle[0] as usize] as usize] = 1;
+        }
+        for state in 1..=m {
+            let fallback = lps_table[state - 1];
+            let row = state * class_count;
+            for class in 0..class_count {
+                transitions[row + class] = transitions[fallback * class_count + class];
+            }
+            if state < m {
+                transitions[row + byte_classes[needle[state] as usize] as usize] = state + 1;
+            }
+        }
+
+        KmpDfa {
+            needle_len: m,
+            byte_classes,
+            class_count,
+            transitions,
+        }
+    }
+
+    /// Comprimento do padrão compilado.
+    pub fn needle_len(&self) -> usize {
+        self.needle_len
+    }
+
+    /// Quantidade de estados do autômato (`needle_len() + 1`).
+    pub fn state_count(&self) -> usize {
+        self.needle_len + 1
+    }
+
+    /// Quantidade de classes de bytes, ou seja, de colunas da tabela de transições.
+    pub fn class_count(&self) -> usize {
+        self.class_count
+    }
+
+    /// Iterador preguiçoso sobre as ocorrências (inclusive sobrepostas) no `haystack`.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> DfaFindIter<'a, 'h> {
+        DfaFindIter {
+            dfa: self,
+            haystack,
+            i: 0,
+            state: 0,
+        }
+    }
+
+    /// Retorna os índices de início de todas as ocorrências, com a mesma semântica de
+    /// `kmp_search`.
+    pub fn find_all(&self, haystack: &[u8]) -> Vec<usize> {
+        self.find_iter(haystack).collect()
+    }
+
+    /// Retorna o índice da primeira ocorrência, parando a busca assim que a encontra.
+    pub fn find_first(&self, haystack: &[u8]) -> Option<usize> {
+        self.find_iter(haystack).next()
+    }
+
+    /// Conta as ocorrências (inclusive sobrepostas) sem alocar um vetor de resultados.
+    pub fn count(&self, haystack: &[u8]) -> usize {
+        self.find_iter(haystack).count()
+    }
+
+    /// Indica se o padrão ocorre pelo menos uma vez no `haystack`.
+    pub fn is_match(&self, haystack: &[u8]) -> bool {
+        self.find_first(haystack).is_some()
+    }
+
+    /// Converte o autômato em um [`DfaStream`], para busca incremental em tempo real.
+    pub fn into_stream(self) -> DfaStream {
+        DfaStream::from_dfa(self)
+    }
+
+    fn next_state(&self, state: usize, byte: u8) -> usize {
+        let class = self.byte_classes[byte as usize] as usize;
+        self.transitions[state * self.class_count + class]
+    }
+}
+
+/// Iterador sobre as ocorrências de um [`KmpDfa`], criado por [`KmpDfa::find_iter`].
+#[derive(Debug, Clone)]
+pub struct DfaFindIter<'a, 'h> {
+    dfa: &'a KmpDfa,
+    haystack: &'h [u8],
+    i: usize,
+    state: usize,
+}
+
+impl Iterator for DfaFindIter<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let m = self.dfa.needle_len;
+        if m == 0 {
+            return None;
+        }
+
+        while self.i < self.haystack.len() {
+            // Uma única consulta à tabela por byte, sem laço de falhas.
+            self.state = self.dfa.next_state(self.state, self.haystack[self.i]);
+            self.i += 1;
+            if self.state == m {
+                return Some(self.i - m);
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for DfaFindIter<'_, '_> {}
+
+/// Busca incremental em tempo real: a variante de [`crate::KmpStream`] sobre o DFA.
+///
+/// No `KmpStream`, o custo é linear apenas no total (amortizado): um único byte pode
+/// disparar vários saltos pela tabela LPS, o que aparece como picos de latência. Aqui,
+/// cada byte custa exatamente uma consulta à tabela de transições, qualquer que seja o
+/// padrão ou a entrada. A interface é a mesma (veja [`StreamSearcher`]), com os mesmos
+/// índices absolutos em `u64`, e [`DfaStream::push`] permite consumir um byte por vez.
+#[derive(Debug, Clone)]
+pub struct DfaStream {
+    dfa: KmpDfa,
+    state: usize,
+    position: u64,
+}
+
+impl DfaStream {
+    /// Cria um stream para o padrão, compilando o seu DFA.
+    pub fn new(needle: &[u8]) -> Self {
+        Self::from_dfa(KmpDfa::new(needle))
+    }
+
+    /// Cria um stream a partir de um DFA já compilado.
+    pub fn from_dfa(dfa: KmpDfa) -> Self {
+        DfaStream {
+            dfa,
+            state: 0,
+            position: 0,
+        }
+    }
+
+    /// Quantos bytes já foram consumidos, ou seja, o índice absoluto do próximo byte.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Volta ao estado inicial, descartando qualquer correspondência parcial.
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.position = 0;
+    }
+
+    /// Consome um único byte, em tempo constante, e retorna o índice absoluto de início da
+    /// ocorrência que termina nele, se houver.
+    pub fn push(&mut self, byte: u8) -> Option<u64> {
+        let m = self.dfa.needle_len;
+        self.position += 1;
+        if m == 0 {
+            return None;
+        }
+
+        self.state = self.dfa.next_state(self.state, byte);
+        if self.state == m {
+            Some(self.position - m as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Consome o próximo pedaço da entrada e retorna os índices absolutos de início das
+    /// ocorrências que terminam dentro dele, inclusive as que começaram em pedaços anteriores.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<u64> {
+        chunk.iter().filter_map(|&byte| self.push(byte)).collect()
+    }
+
+    /// Como [`DfaStream::feed`], mas entrega cada ocorrência a `on_match`, sem alocar.
+    pub fn feed_with<F>(&mut self, chunk: &[u8], mut on_match: F)
+    where
+        F: FnMut(u64),
+    {
+        for &byte in chunk {
+            if let Some(start) = self.push(byte) {
+                on_match(start);
+            }
+        }
+    }
+}
+
+impl StreamSearcher<u8> for DfaStream {
+    fn feed_with(&mut self, chunk: &[u8], on_match: &mut dyn FnMut(u64)) {
+        DfaStream::feed_with(self, chunk, on_match)
+    }
+
+    fn position(&self) -> u64 {
+        DfaStream::position(self)
+    }
+
+    fn reset(&mut self) {
+        DfaStream::reset(self)
+    }
+}
@@ -0,0 +1,224 @@
+//! Busca incremental com capacidade fixa, sem nenhuma alocação.
+//!
+//! O `KmpStream` guarda o padrão e a tabela LPS em `Vec`s. Em firmware, muitas
+//! vezes não há alocador, e o padrão (um delimitador de quadro, um cabeçalho) é conhecido
+//! já na compilação. O [`StaticKmp`] guarda os dois em arrays de tamanho `N` dentro da
+//! própria struct, e o seu construtor para bytes é `const fn`: a tabela de um padrão
+//! literal é calculada pelo compilador, e um padrão maior que `N` é um erro de compilação.
+
+use crate::{advance, compute_lps_into, StreamSearcher};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Busca incremental, com a mesma semântica de `KmpStream`, para padrões de até
+/// `N` elementos guardados inline.
+///
+/// Os índices reportados são absolutos (contados desde o início do stream, ou desde o
+/// último [`StaticKmp::reset`]) e usam `u64`. Para padrões de bytes, o construtor pode ser
+/// avaliado em tempo de compilação:
+///
+/// ```
+/// use hofalgs::static_kmp::StaticKmp;
+///
+/// // A tabela LPS é calculada pelo compilador.
+/// const FRAME_START: StaticKmp<u8, 4> = StaticKmp::new(b"\x7e\x7e");
+///
+/// let mut matcher = FRAME_START;
+/// let mut found = [0; 4];
+/// let mut count = 0;
+/// for chunk in [&b"ab\x7e"[..], b"\x7e\x7ecd"] {
+///     matcher.feed_with(chunk, |start| {
+///         found[count] = start;
+///         count += 1;
+///     });
+/// }
+/// assert_eq!(found[..count], [2, 3]);
+/// ```
+///
+/// Um padrão maior que a capacidade não compila:
+///
+/// ```compile_fail
+/// use hofalgs::static_kmp::StaticKmp;
+///
+/// const TOO_LONG: StaticKmp<u8, 2> = StaticKmp::new(b"abc");
+/// ```
+#[derive(Debug, Clone)]
+pub struct StaticKmp<T, const N: usize> {
+    needle: [T; N],        // só as primeiras `len` posições fazem parte do padrão
+    lps_table: [usize; N], // idem
+    len: usize,
+    j: usize,      // posição atual no needle, preservada entre os pedaços
+    position: u64, // quantos elementos já foram consumidos
+}
+
+impl<const N: usize> StaticKmp<u8, N> {
+    /// Cria o matcher para um padrão de bytes. Por ser `const fn`, pode inicializar uma
+    /// `const` ou `static`, e então a tabela LPS é calculada em tempo de compilação.
+    ///
+    /// # Panics
+    ///
+    /// Entra em pânico se `needle` tiver mais de `N` bytes; em contexto `const`, isso é um
+    /// erro de compilação.
+    pub const fn new(needle: &[u8]) -> Self {
+        assert!(
+            needle.len() <= N,
+            "o padrão é maior que a capacidade do StaticKmp"
+        );
+
+        let mut bytes = [0; N];
+        let mut i = 0;
+        while i < needle.len() {
+            bytes[i] = needle[i];
+            i += 1;
+        }
+
+        // O mesmo laço de `compute_lps_into`, escrito só com o que é permitido em
+        // `const fn` (sem iteradores nem closures).
+        let mut lps_table = [0; N];
+        let mut length = 0;
+        let mut i = 1;
+        while i < needle.len() {
+            if needle[i] == needle[length] {
+                length += 1;
+                lps_table[i] = length;
+                i += 1;
+            } else if length != 0 {
+                length = lps_table[length - 1];
+            } else {
+                lps_table[i] = 0;
+                i += 1;
+            }
+        }
+
+        StaticKmp {
+            needle: bytes,
+            lps_table,
+            len: needle.len(),
+            j: 0,
+            position: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> StaticKmp<T, N>
+where
+    T: PartialEq + Copy + Default,
+{
+    /// Cria o matcher para um padrão de qualquer tipo `Copy`, em tempo de execução. As
+    /// posições não usadas do array são preenchidas com `T::default()`.
+    ///
+    /// # Panics
+    ///
+    /// Entra em pânico se `needle` tiver mais de `N` elementos.
+    pub fn from_slice(needle: &[T]) -> Self {
+        assert!(
+            needle.len() <= N,
+            "o padrão ({}) é maior que a capacidade do StaticKmp ({})",
+            needle.len(),
+            N
+        );
+
+        let mut array = [T::default(); N];
+        array[..needle.len()].copy_from_slice(needle);
+        let mut lps_table = [0; N];
+        compute_lps_into(needle, &mut lps_table);
+
+        StaticKmp {
+            needle: array,
+            lps_table,
+            len: needle.len(),
+            j: 0,
+            position: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> StaticKmp<T, N>
+where
+    T: PartialEq,
+{
+    /// O padrão sendo buscado.
+    pub fn needle(&self) -> &[T] {
+        &self.needle[..self.len]
+    }
+
+    /// O maior padrão que cabe no matcher (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Quantos elementos já foram consumidos, ou seja, o índice absoluto do próximo.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Volta ao estado inicial, descartando qualquer correspondência parcial.
+    pub fn reset(&mut self) {
+        self.j = 0;
+        self.position = 0;
+    }
+
+    /// Consome um único elemento e retorna o índice absoluto de início da ocorrência que
+    /// termina nele, se houver.
+    pub fn push<H>(&mut self, element: &H) -> Option<u64>
+    where
+        H: PartialEq<T>,
+    {
+        self.position += 1;
+        let needle = &self.needle[..self.len];
+        if needle.is_empty() {
+            return None;
+        }
+
+        self.j = advance(needle, &self.lps_table[..], self.j, element);
+        if self.j == needle.len() {
+            self.j = self.lps_table[self.j - 1];
+            Some(self.position - needle.len() as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Consome o próximo pedaço da entrada e entrega a `on_match` os índices absolutos de
+    /// início das ocorrências que terminam dentro dele, inclusive as que começaram em
+    /// pedaços anteriores.
+    pub fn feed_with<H, F>(&mut self, chunk: &[H], mut on_match: F)
+    where
+        H: PartialEq<T>,
+        F: FnMut(u64),
+    {
+        for element in chunk {
+            if let Some(start) = self.push(element) {
+                on_match(start);
+            }
+        }
+    }
+
+    /// Como [`StaticKmp::feed_with`], mas retornando as ocorrências em um `Vec`.
+    #[cfg(feature = "alloc")]
+    pub fn feed<H>(&mut self, chunk: &[H]) -> Vec<u64>
+    where
+        H: PartialEq<T>,
+    {
+        let mut results = Vec::new();
+        self.feed_with(chunk, |start| results.push(start));
+        results
+    }
+}
+
+impl<T, const N: usize> StreamSearcher<T> for StaticKmp<T, N>
+where
+    T: PartialEq,
+{
+    fn feed_with(&mut self, chunk: &[T], on_match: &mut dyn FnMut(u64)) {
+        StaticKmp::feed_with(self, chunk, on_match)
+    }
+
+    fn position(&self) -> u64 {
+        StaticKmp::position(self)
+    }
+
+    fn reset(&mut self) {
+        StaticKmp::reset(self)
+    }
+}
@@ -1,9 +1,83 @@
-// main.rs
+//! Busca de padrões com o algoritmo Knuth-Morris-Pratt (KMP) e parentes.
+//!
+//! A raiz do crate traz o KMP genérico (sobre fatias de qualquer `T: PartialEq`), com as
+//! suas variantes: iteradores preguiçosos, busca reversa, igualdades personalizadas,
+//! padrões pré-compilados (`Kmp`), busca incremental (`KmpStream`) e em arquivos. Os
+//! módulos trazem outros algoritmos com o mesmo contrato de resultados.
+//!
+//! Os itens que dependem de uma feature aparecem aqui sem link, já que nem sempre existem
+//! na documentação gerada.
+//!
+//! # Features
+//!
+//! * `std` (padrão): busca em `io::Read` e em arquivos, e os algoritmos que usam `HashMap`
+//!   (`aho_corasick` e as versões genéricas de `boyer_moore`). Implica `alloc`.
+//! * `alloc`: tudo o que retorna ou guarda um `Vec`. Sem ela, o crate é `#![no_std]` e
+//!   oferece só as variantes que usam buffers do chamador: [`compute_lps_into`],
+//!   [`kmp_search_with`], [`two_way::two_way_find_iter`], a busca incremental de
+//!   capacidade fixa ([`static_kmp::StaticKmp`]) e a interface [`StreamSearcher`].
+//! * `rayon`: busca paralela (`par_kmp_search`).
+//! * `mmap` (padrão): `search_file` com o arquivo mapeado em memória, e `map_file`, para
+//!   mapear um arquivo e buscar nele com qualquer função sobre bytes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+#[cfg(feature = "alloc")]
+use core::iter::Rev;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "std")]
+#[path = "Aho-Corasick.rs"]
+pub mod aho_corasick;
+#[cfg(feature = "alloc")]
+#[path = "Boyer-Moore.rs"]
+pub mod boyer_moore;
+#[path = "Two-Way.rs"]
+pub mod two_way;
+#[cfg(feature = "alloc")]
+#[path = "Z-Algorithm.rs"]
+pub mod z_algorithm;
+#[cfg(feature = "alloc")]
+#[path = "Borders.rs"]
+pub mod borders;
+#[cfg(feature = "alloc")]
+#[path = "Knuth-Morris-Pratt-DFA.rs"]
+pub mod kmp_dfa;
+#[path = "Knuth-Morris-Pratt-Static.rs"]
+pub mod static_kmp;
+#[cfg(feature = "alloc")]
+#[path = "Memchr.rs"]
+mod memchr;
 
 /// Encontra todas as ocorrências de um padrão (needle) dentro de um texto (haystack)
 /// usando o algoritmo Knuth-Morris-Pratt.
 ///
 /// A função é genérica e funciona com qualquer tipo que implemente o trait `PartialEq`.
+/// O `haystack` e o `needle` podem até ter tipos de elementos diferentes, desde que
+/// `T: PartialEq<U>`: é possível, por exemplo, procurar um `&[&str]` em um `&[String]`
+/// sem alocar cópias do padrão. A tabela LPS usa apenas o `PartialEq` do próprio `U`.
 ///
 /// # Argumentos
 ///
@@ -14,57 +88,974 @@
 ///
 /// Retorna um `Vec<usize>` contendo os índices de início de todas as ocorrências
 /// do `needle` no `haystack`. Se nenhuma ocorrência for encontrada, retorna um vetor vazio.
-pub fn kmp_search<T>(haystack: &[T], needle: &[T]) -> Vec<usize>
+#[cfg(feature = "alloc")]
+pub fn kmp_search<T, U>(haystack: &[T], needle: &[U]) -> Vec<usize>
 where
-    T: PartialEq,
+    T: PartialEq<U>,
+    U: PartialEq,
 {
-    // Casos base: se o padrão for vazio ou maior que o texto, não há correspondência.
+    find_iter(haystack, needle).collect()
+}
+
+/// Versão preguiçosa de `kmp_search`: retorna um iterador que produz os índices de início
+/// das ocorrências um a um, em ordem crescente.
+///
+/// Nada é alocado além da tabela LPS, e a busca avança apenas o necessário para produzir
+/// o próximo resultado. Assim, adaptadores como `.take(n)`, `.any(..)` ou `.skip_while(..)`
+/// param de percorrer o `haystack` assim que têm a resposta.
+#[cfg(feature = "alloc")]
+pub fn find_iter<'h, 'n, T, U>(haystack: &'h [T], needle: &'n [U]) -> FindIter<'h, 'n, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    // Casos base: se o padrão for vazio ou maior que o texto, não há correspondência,
+    // e nem vale a pena calcular a tabela LPS.
     if needle.is_empty() || haystack.len() < needle.len() {
-        return vec![];
+        return FindIter::new(&[], needle, Cow::Owned(Vec::new()));
     }
 
-    // 1. Pré-processamento: construir a tabela LPS para o padrão (needle).
-    let lps_table = compute_lps_table(needle);
-    let mut results = Vec::new();
+    // Pré-processamento: construir a tabela LPS para o padrão (needle).
+    FindIter::new(haystack, needle, Cow::Owned(compute_lps_table(needle)))
+}
+
+/// Como `kmp_search`, mas sem nenhuma alocação: a tabela LPS vem do chamador (veja
+/// [`compute_lps_into`]) e cada ocorrência é entregue a `on_match`, em ordem crescente.
+///
+/// É a entrada para ambientes sem alocador (`no_std` sem a feature `alloc`):
+///
+/// ```
+/// let needle = b"aba";
+/// let mut buffer = [0; 8];
+/// let lps = hofalgs::compute_lps_into(needle, &mut buffer);
+///
+/// let mut found = [0; 4];
+/// let mut count = 0;
+/// hofalgs::kmp_search_with(b"abababa", needle, lps, |start| {
+///     found[count] = start;
+///     count += 1;
+/// });
+/// assert_eq!(found[..count], [0, 2, 4]);
+/// ```
+///
+/// # Panics
+///
+/// Entra em pânico se `lps_table` for menor que `needle`. Só as primeiras `needle.len()`
+/// posições são lidas, e elas devem ser a tabela LPS do próprio `needle`.
+pub fn kmp_search_with<T, U, F>(haystack: &[T], needle: &[U], lps_table: &[usize], mut on_match: F)
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+    F: FnMut(usize),
+{
+    let lps_table = &lps_table[..needle.len()];
+    let mut cursor = Cursor::new(haystack.len());
+    while let Some(start) = cursor.next_match(haystack, needle, lps_table, |a, b| a == b) {
+        on_match(start);
+    }
+}
 
-    let mut i = 0; // índice para o haystack
-    let mut j = 0; // índice para o needle
+/// Iterador sobre os índices de início das ocorrências de um padrão, criado por
+/// [`find_iter`] ou [`Kmp::find_iter`].
+///
+/// Guarda os cursores `i` (no haystack) e `j` (no needle) do laço do KMP entre as
+/// chamadas a `next`, retomando a busca exatamente de onde parou.
+///
+/// Também é um `DoubleEndedIterator`: `next_back` roda o KMP da direita para a esquerda,
+/// com a tabela LPS do padrão invertido. Cada ponta calcula a sua tabela só na primeira
+/// chamada, então quem só busca em uma direção não paga pela outra. As duas pontas nunca
+/// reportam a mesma ocorrência, e o iterador termina quando elas se encontram.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindIter<'h, 'n, T, U = T> {
+    haystack: &'h [T],
+    needle: &'n [U],
+    lps_table: Option<Cow<'n, [usize]>>,
+    rev_lps_table: Option<Cow<'n, [usize]>>,
+    // Onde o `Kmp` guarda a tabela invertida, para calculá-la uma vez só entre as buscas.
+    #[cfg(feature = "std")]
+    rev_lps_cache: Option<&'n OnceLock<Vec<usize>>>,
+    strong_table: Option<Cow<'n, [Option<usize>]>>,
+    cursor: Cursor,
+}
 
-    // 2. Busca: percorrer o haystack usando a tabela LPS para saltos inteligentes.
-    while i < haystack.len() {
-        if haystack[i] == needle[j] {
-            // Os caracteres correspondem, avançamos ambos os ponteiros.
-            i += 1;
-            j += 1;
-        }
-
-        if j == needle.len() {
-            // Encontramos uma correspondência completa!
-            // O início da correspondência é `i - j`.
-            results.push(i - j);
-            // Preparamos para a próxima busca usando a tabela LPS para saber onde continuar.
-            j = lps_table[j - 1];
-        } else if i < haystack.len() && haystack[i] != needle[j] {
-            // Os caracteres não correspondem.
-            if j != 0 {
-                // Usamos a tabela LPS para dar um "salto" inteligente no padrão (needle),
-                // evitando retroceder no texto (haystack).
-                j = lps_table[j - 1];
+#[cfg(feature = "alloc")]
+impl<'h, 'n, T, U> FindIter<'h, 'n, T, U> {
+    fn new(haystack: &'h [T], needle: &'n [U], lps_table: Cow<'n, [usize]>) -> Self {
+        FindIter {
+            lps_table: Some(lps_table),
+            ..Self::without_tables(haystack, needle)
+        }
+    }
+
+    /// Um iterador para a busca reversa, com apenas a tabela invertida.
+    fn reversed(haystack: &'h [T], needle: &'n [U], rev_lps_table: Cow<'n, [usize]>) -> Self {
+        FindIter {
+            rev_lps_table: Some(rev_lps_table),
+            ..Self::without_tables(haystack, needle)
+        }
+    }
+
+    fn without_tables(haystack: &'h [T], needle: &'n [U]) -> Self {
+        FindIter {
+            haystack,
+            needle,
+            lps_table: None,
+            rev_lps_table: None,
+            #[cfg(feature = "std")]
+            rev_lps_cache: None,
+            strong_table: None,
+            cursor: Cursor::new(haystack.len()),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn with_rev_lps_cache(mut self, rev_lps_cache: &'n OnceLock<Vec<usize>>) -> Self {
+        self.rev_lps_cache = Some(rev_lps_cache);
+        self
+    }
+
+    fn with_strong_table(mut self, strong_table: Option<Cow<'n, [Option<usize>]>>) -> Self {
+        self.strong_table = strong_table;
+        self
+    }
+
+    /// Quantas comparações entre elementos do `haystack` e do `needle` foram feitas até
+    /// agora, somando as duas pontas da busca.
+    ///
+    /// Serve para comparar as tabelas de falha (veja [`FailureFunction`]): com padrões muito
+    /// repetitivos, a tabela forte evita comparações que a tabela LPS faria à toa.
+    pub fn comparisons(&self) -> u64 {
+        self.cursor.comparisons
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> Iterator for FindIter<'_, '_, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        if let Some(strong_table) = &self.strong_table {
+            return self
+                .cursor
+                .next_match(haystack, needle, &**strong_table, |a, b| a == b);
+        }
+
+        let lps_table = self
+            .lps_table
+            .get_or_insert_with(|| Cow::Owned(compute_lps_table(needle)));
+        self.cursor
+            .next_match(haystack, needle, &**lps_table, |a, b| a == b)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> DoubleEndedIterator for FindIter<'_, '_, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    fn next_back(&mut self) -> Option<usize> {
+        let needle = self.needle;
+        #[cfg(feature = "std")]
+        if let (None, Some(cache)) = (&self.rev_lps_table, self.rev_lps_cache) {
+            let rev_lps_table = cache.get_or_init(|| compute_rev_lps_table(needle));
+            self.rev_lps_table = Some(Cow::Borrowed(rev_lps_table));
+        }
+
+        let rev_lps_table = self
+            .rev_lps_table
+            .get_or_insert_with(|| Cow::Owned(compute_rev_lps_table(needle)));
+        self.cursor
+            .next_match_back(self.haystack, needle, rev_lps_table, |a, b| a == b)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> FusedIterator for FindIter<'_, '_, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+}
+
+/// Estado do laço do KMP, compartilhado por todos os iteradores de busca.
+///
+/// A comparação entre elementos é recebida como parâmetro, para que o mesmo laço sirva
+/// tanto para `PartialEq` quanto para igualdades personalizadas (veja [`kmp_search_by`]).
+// Sem `alloc`, só `kmp_search_with` usa o cursor, e a busca pela direita fica sem uso.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+#[derive(Debug, Clone)]
+struct Cursor {
+    i: usize,         // índice para o haystack
+    j: usize,         // índice para o needle
+    back_i: usize,    // quantos elementos do haystack ainda não foram lidos pela direita
+    back_j: usize,    // comprimento do sufixo do needle já casado pela direita
+    front_min: usize, // menor início ainda não reportado pela frente
+    back_max: usize,  // ocorrências que começam a partir daqui já foram reportadas por trás
+    comparisons: u64, // quantas vezes `eq` foi chamada
+}
+
+impl Cursor {
+    fn new(haystack_len: usize) -> Self {
+        Cursor {
+            i: 0,
+            j: 0,
+            back_i: haystack_len,
+            back_j: 0,
+            front_min: 0,
+            back_max: haystack_len,
+            comparisons: 0,
+        }
+    }
+
+    fn next_match<T, U, F, L>(
+        &mut self,
+        haystack: &[T],
+        needle: &[U],
+        failure_table: &L,
+        eq: F,
+    ) -> Option<usize>
+    where
+        F: Fn(&T, &U) -> bool,
+        L: FailureTable + ?Sized,
+    {
+        self.next_match_prefiltered(haystack, needle, failure_table, eq, |i| i)
+    }
+
+    /// Como `next_match`, mas, sempre que nenhum prefixo do padrão está casado (`j == 0`),
+    /// consulta `prefilter(i)`, que retorna o menor início `>= i` onde uma ocorrência ainda
+    /// é possível (ou `haystack.len()`, se não há nenhum), e salta direto para ele.
+    fn next_match_prefiltered<T, U, F, L, P>(
+        &mut self,
+        haystack: &[T],
+        needle: &[U],
+        failure_table: &L,
+        eq: F,
+        prefilter: P,
+    ) -> Option<usize>
+    where
+        F: Fn(&T, &U) -> bool,
+        L: FailureTable + ?Sized,
+        P: Fn(usize) -> usize,
+    {
+        if needle.is_empty() || haystack.len() < needle.len() {
+            return None;
+        }
+
+        // Busca: percorrer o haystack usando a tabela LPS para saltos inteligentes.
+        // A próxima ocorrência possível começa em `i - j`; se ela já foi reportada pela
+        // outra ponta (`next_back`), não há mais nada a procurar.
+        while self.i < haystack.len() && self.i - self.j < self.back_max {
+            if self.j == 0 {
+                let candidate = prefilter(self.i);
+                if candidate != self.i {
+                    self.i = candidate;
+                    continue;
+                }
+            }
+
+            self.comparisons += 1;
+            if eq(&haystack[self.i], &needle[self.j]) {
+                // Os caracteres correspondem, avançamos ambos os ponteiros.
+                self.i += 1;
+                self.j += 1;
+
+                if self.j == needle.len() {
+                    // Encontramos uma correspondência completa!
+                    // O início da correspondência é `i - j`.
+                    let start = self.i - self.j;
+                    // Preparamos para a próxima busca usando a tabela para saber onde continuar.
+                    self.j = failure_table.fallback(self.j).unwrap_or(0);
+                    self.front_min = start + 1;
+                    return Some(start);
+                }
             } else {
-                // Se `j` já é 0, não há para onde saltar. Apenas avançamos no texto.
-                i += 1;
+                // Os caracteres não correspondem.
+                match failure_table.fallback(self.j) {
+                    // Usamos a tabela para dar um "salto" inteligente no padrão (needle),
+                    // evitando retroceder no texto (haystack).
+                    Some(j) => self.j = j,
+                    // Não há para onde saltar. Apenas avançamos no texto.
+                    None => {
+                        self.j = 0;
+                        self.i += 1;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    #[cfg(feature = "alloc")]
+    fn next_match_back<T, U, F>(
+        &mut self,
+        haystack: &[T],
+        needle: &[U],
+        rev_lps_table: &[usize],
+        eq: F,
+    ) -> Option<usize>
+    where
+        F: Fn(&T, &U) -> bool,
+    {
+        if needle.is_empty() || haystack.len() < needle.len() {
+            return None;
+        }
+        let m = needle.len();
+
+        // O mesmo laço de `next_match`, espelhado: lemos o haystack de trás para frente e
+        // casamos o needle a partir do seu último elemento. O sufixo casado ocupa
+        // `haystack[back_i..back_i + back_j]`, então a próxima ocorrência possível começa
+        // em `back_i + back_j - m`, que não pode ser menor que `front_min`.
+        while self.back_i > 0 && self.back_i + self.back_j >= self.front_min + m {
+            self.comparisons += 1;
+            if eq(&haystack[self.back_i - 1], &needle[m - 1 - self.back_j]) {
+                self.back_i -= 1;
+                self.back_j += 1;
+
+                if self.back_j == m {
+                    let start = self.back_i;
+                    self.back_j = rev_lps_table[self.back_j - 1];
+                    self.back_max = start;
+                    return Some(start);
+                }
+            } else if self.back_j != 0 {
+                self.back_j = rev_lps_table[self.back_j - 1];
+            } else {
+                self.back_i -= 1;
             }
         }
+
+        None
+    }
+}
+
+/// Uma tabela de falhas do KMP: para onde ir no padrão quando a comparação na posição `j`
+/// falha (ou, com `j == needle.len()`, depois de uma ocorrência completa).
+///
+/// `None` significa que nenhum prefixo pode mais casar com o elemento atual do texto: a
+/// busca recomeça do início do padrão no próximo elemento.
+trait FailureTable {
+    fn fallback(&self, j: usize) -> Option<usize>;
+}
+
+/// A tabela LPS: a maior borda do trecho já casado, `lps[j - 1]`.
+impl FailureTable for [usize] {
+    fn fallback(&self, j: usize) -> Option<usize> {
+        j.checked_sub(1).map(|k| self[k])
+    }
+}
+
+/// A tabela forte de [`compute_strong_failure_table`], já indexada por `j`.
+impl FailureTable for [Option<usize>] {
+    fn fallback(&self, j: usize) -> Option<usize> {
+        self[j]
+    }
+}
+
+/// Avança o estado `j` do KMP com o próximo elemento do texto, seguindo a tabela de falhas
+/// enquanto houver divergência. Retorna o novo comprimento do prefixo casado.
+fn advance<H, T, L>(needle: &[T], failure_table: &L, mut j: usize, element: &H) -> usize
+where
+    H: PartialEq<T>,
+    L: FailureTable + ?Sized,
+{
+    loop {
+        if *element == needle[j] {
+            return j + 1;
+        }
+        match failure_table.fallback(j) {
+            Some(next) => j = next,
+            None => return 0,
+        }
+    }
+}
+
+/// Como `kmp_search`, mas usando a função `eq` para comparar os elementos, em vez de
+/// `PartialEq`.
+///
+/// Permite, por exemplo, busca sem diferenciar maiúsculas de minúsculas, ou comparar
+/// registros por apenas alguns dos seus campos. A tabela LPS é calculada com a mesma
+/// função (veja [`compute_lps_table_by`]), de modo que os "saltos" continuam coerentes
+/// com a igualdade escolhida.
+///
+/// `eq` deve ser uma relação de equivalência (reflexiva, simétrica e transitiva): é isso
+/// que garante que os saltos do KMP não pulam nenhuma ocorrência. Comparações por
+/// tolerância, como `|a - b| < 0.01` entre floats, não são transitivas e podem fazer a
+/// busca perder ocorrências; nesse caso, prefira arredondar os valores antes da busca.
+#[cfg(feature = "alloc")]
+pub fn kmp_search_by<T, F>(haystack: &[T], needle: &[T], eq: F) -> Vec<usize>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    find_iter_by(haystack, needle, eq).collect()
+}
+
+/// Versão preguiçosa de `kmp_search_by`.
+#[cfg(feature = "alloc")]
+pub fn find_iter_by<'h, 'n, T, F>(
+    haystack: &'h [T],
+    needle: &'n [T],
+    eq: F,
+) -> FindIterBy<'h, 'n, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return FindIterBy::new(&[], needle, Vec::new(), eq);
+    }
+
+    let lps_table = compute_lps_table_by(needle, &eq);
+    FindIterBy::new(haystack, needle, lps_table, eq)
+}
+
+/// Iterador sobre as ocorrências de um padrão usando uma igualdade personalizada, criado
+/// por [`find_iter_by`]. Assim como [`FindIter`], também pode ser percorrido de trás
+/// para frente.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindIterBy<'h, 'n, T, F> {
+    haystack: &'h [T],
+    needle: &'n [T],
+    lps_table: Vec<usize>,
+    rev_lps_table: Option<Vec<usize>>,
+    cursor: Cursor,
+    eq: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<'h, 'n, T, F> FindIterBy<'h, 'n, T, F> {
+    fn new(haystack: &'h [T], needle: &'n [T], lps_table: Vec<usize>, eq: F) -> Self {
+        FindIterBy {
+            haystack,
+            needle,
+            lps_table,
+            rev_lps_table: None,
+            cursor: Cursor::new(haystack.len()),
+            eq,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> Iterator for FindIterBy<'_, '_, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.cursor
+            .next_match(self.haystack, self.needle, &self.lps_table[..], &self.eq)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> DoubleEndedIterator for FindIterBy<'_, '_, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    fn next_back(&mut self) -> Option<usize> {
+        let (needle, eq) = (self.needle, &self.eq);
+        let rev_lps_table = self.rev_lps_table.get_or_insert_with(|| {
+            let reversed: Vec<&T> = needle.iter().rev().collect();
+            compute_lps_table_by(&reversed, |a, b| eq(a, b))
+        });
+        self.cursor
+            .next_match_back(self.haystack, needle, rev_lps_table, eq)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> FusedIterator for FindIterBy<'_, '_, T, F> where F: Fn(&T, &T) -> bool {}
+
+/// Procura uma sequência de chaves (`needle_keys`) em um `haystack` de elementos de outro
+/// tipo, projetando cada elemento com `key_fn` durante a busca.
+///
+/// Útil para sequências de structs (eventos, tokens com posição...) em que só um campo
+/// importa: não é preciso mapear o `haystack` inteiro para um `Vec` de chaves antes. A
+/// tabela LPS é calculada sobre as próprias chaves, com o `PartialEq` de `K`.
+///
+/// Assim como em `slice::sort_by_key`, `key_fn` pode ser chamada mais de uma vez para o
+/// mesmo elemento (a cada comparação), então deve ser barata e sem efeitos colaterais.
+#[cfg(feature = "alloc")]
+pub fn kmp_search_by_key<T, K, F>(haystack: &[T], needle_keys: &[K], key_fn: F) -> Vec<usize>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    find_iter_by_key(haystack, needle_keys, key_fn).collect()
+}
+
+/// Versão preguiçosa de `kmp_search_by_key`.
+#[cfg(feature = "alloc")]
+pub fn find_iter_by_key<'h, 'n, T, K, F>(
+    haystack: &'h [T],
+    needle_keys: &'n [K],
+    key_fn: F,
+) -> FindIterByKey<'h, 'n, T, K, F>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    let (haystack, lps_table) = if needle_keys.is_empty() || haystack.len() < needle_keys.len() {
+        (&[][..], Vec::new())
+    } else {
+        (haystack, compute_lps_table(needle_keys))
+    };
+
+    FindIterByKey {
+        haystack,
+        needle_keys,
+        lps_table,
+        rev_lps_table: None,
+        cursor: Cursor::new(haystack.len()),
+        key_fn,
+    }
+}
+
+/// Iterador sobre as ocorrências de uma sequência de chaves, criado por [`find_iter_by_key`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindIterByKey<'h, 'n, T, K, F> {
+    haystack: &'h [T],
+    needle_keys: &'n [K],
+    lps_table: Vec<usize>,
+    rev_lps_table: Option<Vec<usize>>,
+    cursor: Cursor,
+    key_fn: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, K, F> Iterator for FindIterByKey<'_, '_, T, K, F>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let key_fn = &self.key_fn;
+        self.cursor.next_match(
+            self.haystack,
+            self.needle_keys,
+            &self.lps_table[..],
+            |element, key| key_fn(element) == *key,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, K, F> DoubleEndedIterator for FindIterByKey<'_, '_, T, K, F>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    fn next_back(&mut self) -> Option<usize> {
+        let (needle_keys, key_fn) = (self.needle_keys, &self.key_fn);
+        let rev_lps_table = self
+            .rev_lps_table
+            .get_or_insert_with(|| compute_rev_lps_table(needle_keys));
+        self.cursor
+            .next_match_back(self.haystack, needle_keys, rev_lps_table, |element, key| {
+                key_fn(element) == *key
+            })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, K, F> FusedIterator for FindIterByKey<'_, '_, T, K, F>
+where
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+}
+
+/// Retorna o índice de início da última ocorrência do padrão, ou `None`.
+///
+/// A busca roda da direita para a esquerda e para na primeira ocorrência encontrada,
+/// sem percorrer o restante do `haystack`. Só a tabela do padrão invertido é calculada.
+#[cfg(feature = "alloc")]
+pub fn rfind<T, U>(haystack: &[T], needle: &[U]) -> Option<usize>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    rfind_iter(haystack, needle).next()
+}
+
+/// Iterador preguiçoso sobre as ocorrências (inclusive sobrepostas) em ordem decrescente,
+/// da última para a primeira.
+#[cfg(feature = "alloc")]
+pub fn rfind_iter<'h, 'n, T, U>(haystack: &'h [T], needle: &'n [U]) -> Rev<FindIter<'h, 'n, T, U>>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    // Mesmos casos base de `find_iter`.
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return FindIter::reversed(&[], needle, Cow::Owned(Vec::new())).rev();
+    }
+
+    FindIter::reversed(haystack, needle, Cow::Owned(compute_rev_lps_table(needle))).rev()
+}
+
+/// Como `kmp_search`, mas reporta apenas ocorrências que não se sobrepõem, com a mesma
+/// semântica de `str::matches`: após cada ocorrência, a busca recomeça logo depois dela.
+///
+/// Por exemplo, procurar `"aba"` em `"abababa"` retorna `[0, 4]`, e não `[0, 2, 4]`.
+/// É o modo adequado para contar tokens ou para fazer substituições.
+#[cfg(feature = "alloc")]
+pub fn kmp_search_non_overlapping<T, U>(haystack: &[T], needle: &[U]) -> Vec<usize>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    find_iter_non_overlapping(haystack, needle).collect()
+}
+
+/// Versão preguiçosa de `kmp_search_non_overlapping`.
+#[cfg(feature = "alloc")]
+pub fn find_iter_non_overlapping<'h, 'n, T, U>(
+    haystack: &'h [T],
+    needle: &'n [U],
+) -> FindNonOverlappingIter<'h, 'n, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    FindNonOverlappingIter {
+        inner: find_iter(haystack, needle),
+    }
+}
+
+/// Iterador sobre ocorrências que não se sobrepõem, criado por [`find_iter_non_overlapping`]
+/// ou [`Kmp::find_iter_non_overlapping`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindNonOverlappingIter<'h, 'n, T, U = T> {
+    inner: FindIter<'h, 'n, T, U>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> Iterator for FindNonOverlappingIter<'_, '_, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let start = self.inner.next()?;
+        // Em vez de continuar a partir da borda `lps_table[j - 1]`, o que permitiria que a
+        // próxima ocorrência reaproveitasse o fim desta, recomeçamos do zero no padrão.
+        self.inner.cursor.j = 0;
+        Some(start)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> FusedIterator for FindNonOverlappingIter<'_, '_, T, U>
+where
+    T: PartialEq<U>,
+    U: PartialEq,
+{
+}
+
+/// Procura `needle` em `haystack` diretamente sobre os bytes UTF-8 das strings, sem
+/// convertê-las para `Vec<char>`.
+///
+/// # Retorno
+///
+/// Retorna as faixas de bytes (`start..end`) de todas as ocorrências, inclusive
+/// sobrepostas, em ordem crescente. Como UTF-8 é auto-sincronizante, toda ocorrência dos
+/// bytes de uma `&str` válida começa e termina em uma fronteira de `char`; assim, as
+/// faixas sempre podem ser usadas para fatiar o `haystack` (`&haystack[range]`). Para
+/// obter índices de `char`, veja [`byte_ranges_to_char_ranges`].
+#[cfg(feature = "alloc")]
+pub fn kmp_find_str(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    find_iter_bytes(haystack.as_bytes(), needle.as_bytes())
+        .map(|start| {
+            let range = start..start + needle.len();
+            debug_assert!(haystack.is_char_boundary(range.start));
+            debug_assert!(haystack.is_char_boundary(range.end));
+            range
+        })
+        .collect()
+}
+
+/// Tamanho mínimo dos pedaços em que [`par_kmp_search`] divide o `haystack`: abaixo disso,
+/// o custo de distribuir o trabalho supera o ganho do paralelismo.
+#[cfg(feature = "rayon")]
+const PAR_MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Versão paralela de `kmp_search`, para haystacks grandes já em memória (disponível com a
+/// feature `rayon`).
+///
+/// O `haystack` é dividido em pedaços, buscados ao mesmo tempo no pool de threads do
+/// rayon, todos com a mesma tabela LPS (calculada uma única vez). Cada pedaço é dono das
+/// ocorrências que *começam* nele, e lê `needle.len() - 1` elementos além do seu fim, o
+/// suficiente para terminar essas ocorrências. Assim, uma ocorrência que atravessa a
+/// fronteira entre dois pedaços é encontrada exatamente uma vez, e juntar os resultados na
+/// ordem dos pedaços já dá o mesmo vetor ordenado e sem repetições da versão sequencial.
+#[cfg(feature = "rayon")]
+pub fn par_kmp_search<T, U>(haystack: &[T], needle: &[U]) -> Vec<usize>
+where
+    T: PartialEq<U> + Sync,
+    U: PartialEq + Sync,
+{
+    use rayon::prelude::*;
+
+    let (n, m) = (haystack.len(), needle.len());
+    if m == 0 || n < m {
+        return vec![];
+    }
+
+    let lps_table = compute_lps_table(needle);
+    // Possíveis inícios de ocorrência: `0..=n - m`.
+    let starts = n - m + 1;
+    let chunk_size = (starts / (rayon::current_num_threads() * 4)).max(PAR_MIN_CHUNK_SIZE);
+
+    let per_chunk: Vec<Vec<usize>> = (0..starts.div_ceil(chunk_size))
+        .into_par_iter()
+        .map(|k| {
+            let begin = k * chunk_size;
+            let end = (begin + chunk_size).min(starts);
+            // Os inícios `begin..end`, mais os `m - 1` elementos que a última ocorrência
+            // possível precisa.
+            let window = &haystack[begin..end + m - 1];
+            FindIter::new(window, needle, Cow::Borrowed(&lps_table[..]))
+                .map(|start| begin + start)
+                .collect()
+        })
+        .collect();
+
+    per_chunk.concat()
+}
+
+/// Como `kmp_search`, mas especializado para bytes: enquanto nenhum prefixo do padrão está
+/// casado, em vez de avançar byte a byte, a busca salta direto para a próxima posição
+/// onde o byte mais raro do padrão aparece, usando uma varredura vetorizada (SSE2/AVX2 em
+/// x86_64, com uma versão portável nas demais arquiteturas).
+///
+/// Os resultados são exatamente os de `kmp_search`; a diferença é a velocidade, que cresce
+/// quanto mais raro for esse byte no `haystack`.
+#[cfg(feature = "alloc")]
+pub fn kmp_search_bytes(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    find_iter_bytes(haystack, needle).collect()
+}
+
+/// Versão preguiçosa de `kmp_search_bytes`.
+#[cfg(feature = "alloc")]
+pub fn find_iter_bytes<'h, 'n>(haystack: &'h [u8], needle: &'n [u8]) -> FindIterBytes<'h, 'n> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return FindIterBytes {
+            haystack: &[],
+            needle,
+            lps_table: Vec::new(),
+            rare_byte: (0, 0),
+            cursor: Cursor::new(0),
+        };
+    }
+
+    FindIterBytes {
+        haystack,
+        needle,
+        lps_table: compute_lps_table(needle),
+        rare_byte: memchr::rare_byte(needle),
+        cursor: Cursor::new(haystack.len()),
+    }
+}
+
+/// Iterador sobre as ocorrências de um padrão de bytes, criado por [`find_iter_bytes`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindIterBytes<'h, 'n> {
+    haystack: &'h [u8],
+    needle: &'n [u8],
+    lps_table: Vec<usize>,
+    rare_byte: (usize, u8), // posição no needle e valor do byte usado no pré-filtro
+    cursor: Cursor,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for FindIterBytes<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let haystack = self.haystack;
+        let (offset, byte) = self.rare_byte;
+        // Uma ocorrência que começa em `start` tem `byte` em `start + offset`: a primeira
+        // posição possível é a do próximo `byte` a partir de `i + offset`.
+        let prefilter = |i: usize| {
+            haystack
+                .get(i + offset..)
+                .and_then(|rest| memchr::memchr(byte, rest))
+                .map_or(haystack.len(), |k| i + k)
+        };
+        self.cursor.next_match_prefiltered(
+            haystack,
+            self.needle,
+            &self.lps_table[..],
+            |a, b| a == b,
+            prefilter,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for FindIterBytes<'_, '_> {}
+
+/// Como `kmp_search`, mas especializado para `char`s, com o mesmo pré-filtro de
+/// `kmp_search_bytes`: enquanto nenhum prefixo do padrão está casado, a busca salta para a
+/// próxima posição onde o `char` mais raro do padrão aparece.
+///
+/// Aqui a varredura é escalar (um `char` por vez), mas ainda evita passar pelo laço do KMP
+/// e pela tabela de falhas em cada posição. Os resultados são exatamente os de
+/// `kmp_search`.
+#[cfg(feature = "alloc")]
+pub fn kmp_search_chars(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    find_iter_chars(haystack, needle).collect()
+}
+
+/// Versão preguiçosa de `kmp_search_chars`.
+#[cfg(feature = "alloc")]
+pub fn find_iter_chars<'h, 'n>(haystack: &'h [char], needle: &'n [char]) -> FindIterChars<'h, 'n> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return FindIterChars {
+            haystack: &[],
+            needle,
+            lps_table: Vec::new(),
+            rare_char: (0, '\0'),
+            cursor: Cursor::new(0),
+        };
+    }
+
+    FindIterChars {
+        haystack,
+        needle,
+        lps_table: compute_lps_table(needle),
+        rare_char: memchr::rare_char(needle),
+        cursor: Cursor::new(haystack.len()),
+    }
+}
+
+/// Iterador sobre as ocorrências de um padrão de `char`s, criado por [`find_iter_chars`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct FindIterChars<'h, 'n> {
+    haystack: &'h [char],
+    needle: &'n [char],
+    lps_table: Vec<usize>,
+    rare_char: (usize, char), // posição no needle e valor do `char` usado no pré-filtro
+    cursor: Cursor,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for FindIterChars<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let haystack = self.haystack;
+        let (offset, ch) = self.rare_char;
+        // O mesmo raciocínio de `FindIterBytes`, com uma varredura escalar.
+        let prefilter = |i: usize| {
+            haystack
+                .get(i + offset..)
+                .and_then(|rest| rest.iter().position(|&c| c == ch))
+                .map_or(haystack.len(), |k| i + k)
+        };
+        self.cursor.next_match_prefiltered(
+            haystack,
+            self.needle,
+            &self.lps_table[..],
+            |a, b| a == b,
+            prefilter,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for FindIterChars<'_, '_> {}
+
+/// Converte faixas de bytes (como as retornadas por [`kmp_find_str`]) em faixas de índices
+/// de `char` no mesmo `haystack`.
+///
+/// O `haystack` é percorrido uma única vez quando as faixas estão em ordem crescente, que é
+/// o caso das retornadas por `kmp_find_str`; faixas fora de ordem também funcionam, só que
+/// mais devagar.
+///
+/// # Panics
+///
+/// Entra em pânico se alguma extremidade não estiver em uma fronteira de `char`, ou estiver
+/// além do fim do `haystack`.
+#[cfg(feature = "alloc")]
+pub fn byte_ranges_to_char_ranges(haystack: &str, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+    // Um cursor (offset em bytes, índice em chars) para os inícios e outro para os fins,
+    // para que cada um só avance sobre o trecho ainda não contado.
+    let mut starts = (0, 0);
+    let mut ends = (0, 0);
+    ranges
+        .iter()
+        .map(|range| {
+            let start = char_index_at(haystack, &mut starts, range.start);
+            let end = char_index_at(haystack, &mut ends, range.end);
+            start..end
+        })
+        .collect()
+}
+
+/// Avança o cursor `(offset em bytes, índice em chars)` até `byte_offset`, retornando o
+/// índice de `char` correspondente.
+#[cfg(feature = "alloc")]
+fn char_index_at(haystack: &str, cursor: &mut (usize, usize), byte_offset: usize) -> usize {
+    if byte_offset < cursor.0 {
+        // Fora de ordem: recomeçamos a contagem do início.
+        *cursor = (0, 0);
     }
+    cursor.1 += haystack[cursor.0..byte_offset].chars().count();
+    cursor.0 = byte_offset;
+    cursor.1
+}
 
-    results
+/// Calcula a tabela LPS do padrão invertido, usada pela busca da direita para a esquerda.
+#[cfg(feature = "alloc")]
+fn compute_rev_lps_table<T>(needle: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let reversed: Vec<&T> = needle.iter().rev().collect();
+    compute_lps_table(&reversed)
 }
 
-/// Função auxiliar para calcular a tabela LPS (Longest Proper Prefix which is also Suffix).
-/// Esta tabela é o coração do KMP, permitindo os "saltos" eficientes.
-fn compute_lps_table<T>(needle: &[T]) -> Vec<usize>
+/// Calcula a tabela LPS (Longest Proper Prefix which is also Suffix), também conhecida
+/// como função de prefixo. Esta tabela é o coração do KMP, permitindo os "saltos" eficientes.
+///
+/// `lps[i]` é o comprimento da maior borda de `needle[..=i]`, isto é, do maior prefixo
+/// próprio que também é sufixo desse trecho. Além da busca, a tabela responde perguntas
+/// sobre a estrutura da própria sequência; veja o módulo [`borders`].
+#[cfg(feature = "alloc")]
+pub fn compute_lps_table<T>(needle: &[T]) -> Vec<usize>
 where
     T: PartialEq,
+{
+    compute_lps_table_by(needle, |a, b| a == b)
+}
+
+/// Como `compute_lps_table`, mas comparando os elementos do padrão com `eq`.
+///
+/// A tabela precisa usar a mesma igualdade da busca: um prefixo que só é "igual" a um
+/// sufixo segundo `eq` também é uma borda válida para os saltos do KMP.
+#[cfg(feature = "alloc")]
+pub fn compute_lps_table_by<T, F>(needle: &[T], eq: F) -> Vec<usize>
+where
+    F: Fn(&T, &T) -> bool,
 {
     if needle.is_empty() {
         return vec![];
@@ -72,12 +1063,55 @@ where
     
     // A tabela LPS terá o mesmo tamanho do padrão.
     let mut lps = vec![0; needle.len()];
+    compute_lps_into_by(needle, &mut lps, eq);
+    lps
+}
+
+/// Como `compute_lps_table`, mas escreve a tabela em um buffer do chamador, sem alocar.
+///
+/// Só as primeiras `needle.len()` posições de `lps` são usadas; a parte preenchida é
+/// retornada, pronta para ser passada a [`kmp_search_with`]. Assim, um buffer de tamanho
+/// fixo (por exemplo, `[0; 64]`) serve para qualquer padrão de até 64 elementos.
+///
+/// # Panics
+///
+/// Entra em pânico se `lps` for menor que `needle`.
+pub fn compute_lps_into<'a, T>(needle: &[T], lps: &'a mut [usize]) -> &'a [usize]
+where
+    T: PartialEq,
+{
+    compute_lps_into_by(needle, lps, |a, b| a == b)
+}
+
+/// Como [`compute_lps_into`], mas comparando os elementos do padrão com `eq` (veja
+/// `compute_lps_table_by`).
+///
+/// # Panics
+///
+/// Entra em pânico se `lps` for menor que `needle`.
+pub fn compute_lps_into_by<'a, T, F>(needle: &[T], lps: &'a mut [usize], eq: F) -> &'a [usize]
+where
+    F: Fn(&T, &T) -> bool,
+{
+    assert!(
+        lps.len() >= needle.len(),
+        "o buffer da tabela LPS ({}) é menor que o padrão ({})",
+        lps.len(),
+        needle.len()
+    );
+    let lps = &mut lps[..needle.len()];
+    if needle.is_empty() {
+        return lps;
+    }
+
+    // O buffer pode ter lixo de um uso anterior: só `lps[0]` não é escrito pelo laço.
+    lps[0] = 0;
     let mut length = 0; // Comprimento do maior prefixo-sufixo anterior.
     let mut i = 1;
 
     // O loop calcula lps[i] para i de 1 a n-1. lps[0] é sempre 0.
     while i < needle.len() {
-        if needle[i] == needle[length] {
+        if eq(&needle[i], &needle[length]) {
             // Se correspondem, o novo comprimento do prefixo-sufixo é o anterior + 1.
             length += 1;
             lps[i] = length;
@@ -99,53 +1133,640 @@ where
     lps
 }
 
+/// Calcula a tabela de falhas "forte" (a função `next` de Knuth) do padrão.
+///
+/// Com a tabela LPS, uma divergência em `needle[j]` leva a `j = lps[j - 1]`; mas, se
+/// `needle[lps[j - 1]] == needle[j]`, a próxima comparação falha de novo com certeza. A
+/// tabela forte já pula esses saltos inúteis: `table[j]` é a maior borda `k` de
+/// `needle[..j]` com `needle[k] != needle[j]`, ou `None` se não existe nenhuma (e então o
+/// texto avança direto). A tabela tem `needle.len() + 1` posições; a última, usada após
+/// uma ocorrência completa, é a própria borda `lps[m - 1]`.
+///
+/// Os resultados da busca são os mesmos; só o número de comparações muda. Veja
+/// [`FailureFunction`].
+#[cfg(feature = "alloc")]
+pub fn compute_strong_failure_table<T>(needle: &[T]) -> Vec<Option<usize>>
+where
+    T: PartialEq,
+{
+    let m = needle.len();
+    let lps_table = compute_lps_table(needle);
+    let mut table = vec![None; m + 1];
 
-// --- Exemplo de Uso ---
-fn main() {
-    // Exemplo 1: Busca de texto (string)
-    let text = "ABABCABABABCD";
-    let pattern = "ABABCD";
-    
-    // Convertendo para vetores de char para usar a função genérica
-    let text_chars: Vec<char> = text.chars().collect();
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-
-    let matches = kmp_search(&text_chars, &pattern_chars);
-    println!("Texto: '{}'", text);
-    println!("Padrão: '{}'", pattern);
-    println!("Padrão encontrado nos índices: {:?}", matches); // Deve imprimir [7]
-    println!("---");
-
-    // Exemplo 2: Múltiplas ocorrências, incluindo sobrepostas
-    let text2 = "abababa";
-    let pattern2 = "aba";
-    let text_chars2: Vec<char> = text2.chars().collect();
-    let pattern_chars2: Vec<char> = pattern2.chars().collect();
-    
-    let matches2 = kmp_search(&text_chars2, &pattern_chars2);
-    println!("Texto: '{}'", text2);
-    println!("Padrão: '{}'", pattern2);
-    println!("Padrão encontrado nos índices: {:?}", matches2); // Deve imprimir [0, 2, 4]
-    println!("---");
-
-    // Exemplo 3: Genérico, usando números (u8)
-    let sequence: Vec<u8> = vec![1, 2, 3, 1, 2, 4, 5, 1, 2, 3, 1, 2, 3, 5];
-    let sub_sequence: Vec<u8> = vec![1, 2, 3, 5];
-
-    let matches3 = kmp_search(&sequence, &sub_sequence);
-    println!("Sequência: {:?}", sequence);
-    println!("Sub-sequência: {:?}", sub_sequence);
-    println!("Sub-sequência encontrada nos índices: {:?}", matches3); // Deve imprimir [9]
-    println!("---");
-    
-    // Exemplo 4: Sem ocorrências
-    let text4 = "abcdefg";
-    let pattern4 = "xyz";
-    let text_chars4: Vec<char> = text4.chars().collect();
-    let pattern_chars4: Vec<char> = pattern4.chars().collect();
-
-    let matches4 = kmp_search(&text_chars4, &pattern_chars4);
-    println!("Texto: '{}'", text4);
-    println!("Padrão: '{}'", pattern4);
-    println!("Padrão encontrado nos índices: {:?}", matches4); // Deve imprimir []
+    for j in 1..m {
+        let border = lps_table[j - 1];
+        // Se a borda continua com o mesmo elemento, ela falharia pelo mesmo motivo:
+        // herdamos o salto que ela mesma daria (já calculado, pois `border < j`).
+        table[j] = if needle[border] == needle[j] {
+            table[border]
+        } else {
+            Some(border)
+        };
+    }
+    if m > 0 {
+        table[m] = Some(lps_table[m - 1]);
+    }
+
+    table
+}
+
+/// Qual tabela de falhas um [`Kmp`] usa na busca da esquerda para a direita.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureFunction {
+    /// A tabela LPS clássica (veja [`compute_lps_table`]).
+    #[default]
+    Lps,
+    /// A tabela forte de Knuth (veja [`compute_strong_failure_table`]), que evita
+    /// comparações fadadas a falhar. Costuma fazer diferença em padrões muito repetitivos,
+    /// como `"aaaa"`; a busca da direita para a esquerda continua usando a tabela LPS.
+    Strong,
+}
+
+/// Um padrão KMP pré-compilado: guarda o `needle` junto com a sua tabela LPS.
+///
+/// Construa uma vez com [`Kmp::new`] e reutilize em quantos textos forem necessários,
+/// sem pagar o custo do pré-processamento a cada busca. O tipo é `Clone`, e também
+/// `Send + Sync` sempre que `T` for, de modo que um único `Kmp` pode ser compartilhado
+/// entre threads (por exemplo, dentro de um `Arc`).
+///
+/// A tabela do padrão invertido, usada só pelas buscas da direita para a esquerda
+/// ([`Kmp::rfind`] e afins), é calculada na primeira delas e guardada para as próximas.
+/// Sem a feature `std`, ela é recalculada a cada iterador reverso.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Kmp<T> {
+    needle: Vec<T>,
+    lps_table: Vec<usize>,
+    #[cfg(feature = "std")]
+    rev_lps_table: OnceLock<Vec<usize>>, // calculada na primeira busca reversa
+    strong_table: Option<Vec<Option<usize>>>, // só com `FailureFunction::Strong`
+}
+
+// A igualdade ignora o cache da tabela invertida: ela depende só do padrão, e dois
+// `Kmp` iguais não devem diferir só porque um deles já buscou de trás para frente.
+#[cfg(feature = "alloc")]
+impl<T> PartialEq for Kmp<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.needle == other.needle
+            && self.lps_table == other.lps_table
+            && self.strong_table == other.strong_table
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Eq for Kmp<T> where T: Eq {}
+
+#[cfg(feature = "alloc")]
+impl<T> Kmp<T>
+where
+    T: PartialEq,
+{
+    /// Compila o padrão, copiando o `needle` e calculando a sua tabela LPS.
+    pub fn new(needle: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(needle.to_vec())
+    }
+
+    /// Compila o padrão a partir de um `Vec` já existente, sem copiá-lo.
+    pub fn from_vec(needle: Vec<T>) -> Self {
+        Self::from_vec_with_failure_function(needle, FailureFunction::Lps)
+    }
+
+    /// Compila o padrão com a tabela de falhas escolhida.
+    pub fn with_failure_function(needle: &[T], failure_function: FailureFunction) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec_with_failure_function(needle.to_vec(), failure_function)
+    }
+
+    /// Como [`Kmp::with_failure_function`], mas a partir de um `Vec` já existente.
+    pub fn from_vec_with_failure_function(
+        needle: Vec<T>,
+        failure_function: FailureFunction,
+    ) -> Self {
+        let lps_table = compute_lps_table(&needle);
+        let strong_table = match failure_function {
+            FailureFunction::Lps => None,
+            FailureFunction::Strong => Some(compute_strong_failure_table(&needle)),
+        };
+        Kmp {
+            needle,
+            lps_table,
+            #[cfg(feature = "std")]
+            rev_lps_table: OnceLock::new(),
+            strong_table,
+        }
+    }
+
+    /// O padrão (needle) compilado.
+    pub fn needle(&self) -> &[T] {
+        &self.needle
+    }
+
+    /// A tabela de falhas usada na busca.
+    pub fn failure_function(&self) -> FailureFunction {
+        if self.strong_table.is_some() {
+            FailureFunction::Strong
+        } else {
+            FailureFunction::Lps
+        }
+    }
+
+    /// Iterador preguiçoso sobre as ocorrências no `haystack`, reutilizando as tabelas
+    /// já calculadas. Veja [`find_iter`].
+    pub fn find_iter<'h, H>(&self, haystack: &'h [H]) -> FindIter<'h, '_, H, T>
+    where
+        H: PartialEq<T>,
+    {
+        let iter = FindIter::new(haystack, &self.needle, Cow::Borrowed(&self.lps_table))
+            .with_strong_table(self.strong_table.as_deref().map(Cow::Borrowed));
+        #[cfg(feature = "std")]
+        let iter = iter.with_rev_lps_cache(&self.rev_lps_table);
+        iter
+    }
+
+    /// Iterador preguiçoso sobre as ocorrências que não se sobrepõem.
+    /// Veja [`find_iter_non_overlapping`].
+    pub fn find_iter_non_overlapping<'h, H>(
+        &self,
+        haystack: &'h [H],
+    ) -> FindNonOverlappingIter<'h, '_, H, T>
+    where
+        H: PartialEq<T>,
+    {
+        FindNonOverlappingIter {
+            inner: self.find_iter(haystack),
+        }
+    }
+
+    /// Retorna os índices de início de todas as ocorrências (inclusive sobrepostas),
+    /// com a mesma semântica de `kmp_search`.
+    pub fn find_all<H>(&self, haystack: &[H]) -> Vec<usize>
+    where
+        H: PartialEq<T>,
+    {
+        self.find_iter(haystack).collect()
+    }
+
+    /// Retorna o índice da primeira ocorrência, parando a busca assim que a encontra.
+    pub fn find_first<H>(&self, haystack: &[H]) -> Option<usize>
+    where
+        H: PartialEq<T>,
+    {
+        self.find_iter(haystack).next()
+    }
+
+    /// Conta as ocorrências (inclusive sobrepostas) sem alocar um vetor de resultados.
+    pub fn count<H>(&self, haystack: &[H]) -> usize
+    where
+        H: PartialEq<T>,
+    {
+        self.find_iter(haystack).count()
+    }
+
+    /// Retorna o índice da última ocorrência, buscando da direita para a esquerda.
+    pub fn rfind<H>(&self, haystack: &[H]) -> Option<usize>
+    where
+        H: PartialEq<T>,
+    {
+        self.find_iter(haystack).next_back()
+    }
+
+    /// Iterador sobre as ocorrências em ordem decrescente. Veja [`rfind_iter`].
+    pub fn rfind_iter<'h, H>(&self, haystack: &'h [H]) -> Rev<FindIter<'h, '_, H, T>>
+    where
+        H: PartialEq<T>,
+    {
+        self.find_iter(haystack).rev()
+    }
+
+    /// Indica se o padrão ocorre pelo menos uma vez no `haystack`.
+    pub fn is_match<H>(&self, haystack: &[H]) -> bool
+    where
+        H: PartialEq<T>,
+    {
+        self.find_first(haystack).is_some()
+    }
+
+    /// Converte o padrão compilado em um [`KmpStream`], para busca incremental.
+    pub fn into_stream(self) -> KmpStream<T> {
+        KmpStream::from_kmp(self)
+    }
+}
+
+/// Busca incremental (em streaming) sobre uma entrada que chega em pedaços (chunks).
+///
+/// Como o KMP nunca retrocede no texto, basta carregar a posição `j` no padrão de uma
+/// chamada de [`KmpStream::feed`] para a próxima: ocorrências que atravessam a fronteira
+/// entre dois pedaços são encontradas sem guardar nenhum pedaço anterior.
+///
+/// Os índices reportados são absolutos (contados desde o início do stream, ou desde o
+/// último [`KmpStream::reset`]) e usam `u64`, já que um stream pode ser mais longo do que
+/// qualquer fatia endereçável em memória.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct KmpStream<T> {
+    kmp: Kmp<T>,
+    j: usize,      // posição atual no needle, preservada entre os pedaços
+    position: u64, // quantos elementos já foram consumidos
+}
+
+#[cfg(feature = "alloc")]
+impl<T> KmpStream<T>
+where
+    T: PartialEq,
+{
+    /// Cria um stream para o padrão, copiando o `needle` e calculando a sua tabela LPS.
+    pub fn new(needle: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_kmp(Kmp::new(needle))
+    }
+
+    /// Cria um stream a partir de um padrão já compilado.
+    pub fn from_kmp(kmp: Kmp<T>) -> Self {
+        KmpStream {
+            kmp,
+            j: 0,
+            position: 0,
+        }
+    }
+
+    /// O padrão (needle) procurado.
+    pub fn needle(&self) -> &[T] {
+        self.kmp.needle()
+    }
+
+    /// Quantos elementos já foram consumidos, ou seja, o índice absoluto do início do
+    /// próximo pedaço.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Volta ao estado inicial, descartando qualquer correspondência parcial.
+    pub fn reset(&mut self) {
+        self.j = 0;
+        self.position = 0;
+    }
+
+    /// Consome o próximo pedaço da entrada e retorna os índices absolutos de início das
+    /// ocorrências que terminam dentro dele, inclusive as que começaram em pedaços anteriores.
+    pub fn feed<H>(&mut self, chunk: &[H]) -> Vec<u64>
+    where
+        H: PartialEq<T>,
+    {
+        let mut results = Vec::new();
+        self.feed_with(chunk, |start| results.push(start));
+        results
+    }
+
+    /// Como [`KmpStream::feed`], mas entrega cada ocorrência a `on_match`, sem alocar um
+    /// vetor de resultados.
+    pub fn feed_with<H, F>(&mut self, chunk: &[H], mut on_match: F)
+    where
+        H: PartialEq<T>,
+        F: FnMut(u64),
+    {
+        let needle = &self.kmp.needle;
+        let lps_table = &self.kmp.lps_table;
+
+        if !needle.is_empty() {
+            for (k, element) in chunk.iter().enumerate() {
+                // Em caso de divergência, seguimos a tabela de falhas até achar um prefixo
+                // que ainda possa ser estendido por `element` (ou até esgotar as opções).
+                self.j = match &self.kmp.strong_table {
+                    Some(strong_table) => advance(needle, &strong_table[..], self.j, element),
+                    None => advance(needle, &lps_table[..], self.j, element),
+                };
+
+                if self.j == needle.len() {
+                    // A ocorrência termina em `k`; o início pode estar em um pedaço anterior.
+                    let end = self.position + k as u64 + 1;
+                    on_match(end - needle.len() as u64);
+                    self.j = lps_table[self.j - 1];
+                }
+            }
+        }
+
+        self.position += chunk.len() as u64;
+    }
+}
+
+/// Interface comum das buscas incrementais, implementada por `KmpStream` e
+/// `kmp_dfa::DfaStream` (com a feature `alloc`) e por [`static_kmp::StaticKmp`].
+///
+/// Permite escrever consumidores que recebem qualquer uma das variantes: a busca normal,
+/// com custo amortizado linear, ou a de tempo real, com custo limitado por elemento.
+///
+/// Só `feed_with` precisa ser implementado; `feed`, que junta as ocorrências em um `Vec`,
+/// vem de graça com a feature `alloc`.
+pub trait StreamSearcher<T> {
+    /// Consome o próximo pedaço da entrada e entrega a `on_match` os índices absolutos de
+    /// início das ocorrências que terminam dentro dele, sem alocar.
+    fn feed_with(&mut self, chunk: &[T], on_match: &mut dyn FnMut(u64));
+
+    /// Consome o próximo pedaço da entrada e retorna os índices absolutos de início das
+    /// ocorrências que terminam dentro dele.
+    #[cfg(feature = "alloc")]
+    fn feed(&mut self, chunk: &[T]) -> Vec<u64> {
+        let mut results = Vec::new();
+        self.feed_with(chunk, &mut |start| results.push(start));
+        results
+    }
+
+    /// Quantos elementos já foram consumidos desde o início (ou desde o último `reset`).
+    fn position(&self) -> u64;
+
+    /// Volta ao estado inicial, descartando qualquer correspondência parcial.
+    fn reset(&mut self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> StreamSearcher<T> for KmpStream<T>
+where
+    T: PartialEq,
+{
+    fn feed_with(&mut self, chunk: &[T], on_match: &mut dyn FnMut(u64)) {
+        KmpStream::feed_with(self, chunk, on_match)
+    }
+
+    fn position(&self) -> u64 {
+        KmpStream::position(self)
+    }
+
+    fn reset(&mut self) {
+        KmpStream::reset(self)
+    }
+}
+
+/// Tamanho dos buffers usados por [`search_reader`] para ler a entrada.
+#[cfg(feature = "std")]
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Procura um padrão de bytes diretamente em uma fonte `std::io::Read` (arquivos, sockets,
+/// `BufReader`, `stdin`...), sem carregar a entrada inteira em memória.
+///
+/// A entrada é lida em buffers de tamanho fixo e o estado do KMP é carregado de um buffer
+/// para o outro (veja [`KmpStream`]), de modo que ocorrências na fronteira entre dois
+/// buffers também são encontradas.
+///
+/// # Retorno
+///
+/// Retorna um iterador de `io::Result<u64>` com os deslocamentos (offsets) em bytes de
+/// cada ocorrência, desde o início do reader. Erros de leitura são repassados como `Err`
+/// (após o qual o iterador termina), e `ErrorKind::Interrupted` é tratado com uma nova
+/// tentativa. Os offsets são `u64` para que entradas maiores que 4 GiB funcionem mesmo
+/// em plataformas de 32 bits.
+#[cfg(feature = "std")]
+pub fn search_reader<R>(reader: R, needle: &[u8]) -> ReaderMatches<R>
+where
+    R: Read,
+{
+    ReaderMatches {
+        reader,
+        stream: KmpStream::new(needle),
+        buffer: vec![0; READ_BUFFER_SIZE],
+        pending: VecDeque::new(),
+        // Um padrão vazio não tem ocorrências: nem chegamos a ler a entrada.
+        done: needle.is_empty(),
+    }
+}
+
+/// Iterador sobre as ocorrências de um padrão em um reader, criado por [`search_reader`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ReaderMatches<R> {
+    reader: R,
+    stream: KmpStream<u8>,
+    buffer: Vec<u8>,
+    pending: VecDeque<u64>, // ocorrências do último buffer ainda não entregues
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R> Iterator for ReaderMatches<R>
+where
+    R: Read,
+{
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<io::Result<u64>> {
+        loop {
+            if let Some(offset) = self.pending.pop_front() {
+                return Some(Ok(offset));
+            }
+            if self.done {
+                return None;
+            }
+
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(n) => self.pending.extend(self.stream.feed(&self.buffer[..n])),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> FusedIterator for ReaderMatches<R> where R: Read {}
+
+/// Procura um padrão de bytes em um arquivo inteiro, retornando os offsets (em bytes) de
+/// todas as ocorrências, inclusive sobrepostas, em ordem crescente.
+///
+/// Com a feature `mmap`, o arquivo é mapeado em memória e varrido diretamente (com o
+/// pré-filtro de [`kmp_search_bytes`]), sem copiá-lo para um `Vec`: o sistema operacional
+/// carrega as páginas sob demanda e pode descartá-las depois. Sem a feature, ou quando o
+/// mapeamento não é possível (pipes, arquivos especiais, arquivos que informam tamanho
+/// zero mas têm conteúdo, como os de `/proc`), o arquivo é lido em blocos com
+/// [`search_reader`], usando memória constante. Um arquivo vazio não tem ocorrências.
+///
+/// # Mapeamento em memória
+///
+/// O arquivo não pode ser alterado por outro processo durante a busca: um arquivo
+/// truncado no meio da varredura pode derrubar o processo (`SIGBUS`), e alterações no
+/// conteúdo podem produzir resultados inconsistentes.
+#[cfg(feature = "std")]
+pub fn search_file<P>(path: P, needle: &[u8]) -> io::Result<Vec<u64>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(path)?;
+
+    // SAFETY: o mapeamento só é lido durante esta chamada, e a documentação acima exige
+    // que o arquivo não seja alterado nesse intervalo.
+    #[cfg(feature = "mmap")]
+    if let Some(map) = unsafe { map_file(&file)? } {
+        return Ok(find_iter_bytes(&map, needle)
+            .map(|start| start as u64)
+            .collect());
+    }
+
+    search_reader(file, needle).collect()
+}
+
+/// O conteúdo de um arquivo mapeado em memória, criado por [`map_file`]. Derreferencia
+/// para `[u8]`, então serve direto para qualquer busca em fatias de bytes.
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MappedFile {
+    map: memmap2::Mmap,
+}
+
+#[cfg(feature = "mmap")]
+impl core::ops::Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+/// Mapeia `file` em memória para leitura, como faz [`search_file`].
+///
+/// Retorna `Ok(None)` quando o mapeamento não é possível ou não serve: arquivos vazios,
+/// pipes, arquivos especiais e os que informam tamanho zero mas têm conteúdo (como os de
+/// `/proc`). Nesses casos, leia o arquivo normalmente, por exemplo com [`search_reader`].
+///
+/// # Safety
+///
+/// O arquivo não pode ser alterado por outro processo enquanto o [`MappedFile`] existir:
+/// um arquivo truncado pode derrubar o processo (`SIGBUS`), e alterações no conteúdo
+/// violam a imutabilidade da fatia `&[u8]`.
+#[cfg(feature = "mmap")]
+pub unsafe fn map_file(file: &File) -> io::Result<Option<MappedFile>> {
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    // SAFETY: repassada ao chamador (veja acima).
+    let map = unsafe { memmap2::Mmap::map(file) };
+    Ok(map.ok().map(|map| MappedFile { map }))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{find_iter, find_iter_by, kmp_search, kmp_search_chars, FailureFunction, Kmp};
+    use alloc::vec::Vec;
+
+    /// Gerador xorshift, para ter entradas "aleatórias" reprodutíveis sem dependências.
+    fn next(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    /// Todas as ocorrências, inclusive sobrepostas, pela definição.
+    fn naive<F>(haystack: &[u8], needle: &[u8], eq: F) -> Vec<usize>
+    where
+        F: Fn(&u8, &u8) -> bool,
+    {
+        if needle.is_empty() || haystack.len() < needle.len() {
+            return Vec::new();
+        }
+        Vec::from_iter(
+            (0..=haystack.len() - needle.len())
+                .filter(|&start| needle.iter().zip(&haystack[start..]).all(|(a, b)| eq(b, a))),
+        )
+    }
+
+    /// Consome o iterador alternando `next` e `next_back` ao acaso e confere que as duas
+    /// pontas, juntas, produzem exatamente `expected`, sem repetir nem pular nada.
+    fn check<I>(mut iter: I, expected: &[usize], seed: &mut u64)
+    where
+        I: DoubleEndedIterator<Item = usize>,
+    {
+        let (mut front, mut back) = (Vec::new(), Vec::new());
+        loop {
+            let (end, found) = if next(seed) & 1 == 0 {
+                (&mut front, iter.next())
+            } else {
+                (&mut back, iter.next_back())
+            };
+            match found {
+                Some(start) => end.push(start),
+                None => break,
+            }
+        }
+        // Uma ponta esgotada esgota a outra, e o iterador continua esgotado.
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        front.extend(back.iter().rev());
+        assert_eq!(front, expected);
+    }
+
+    /// Casos aleatórios sobre um alfabeto pequeno, para ter muitas ocorrências sobrepostas.
+    fn cases(mut seed: u64, alphabet: &[u8]) -> impl Iterator<Item = (Vec<u8>, Vec<u8>, u64)> + '_ {
+        (0..3000).map(move |_| {
+            let haystack_len = (next(&mut seed) % 40) as usize;
+            let needle_len = (next(&mut seed) % 6) as usize;
+            let mut pick = |len| {
+                Vec::from_iter(
+                    (0..len).map(|_| alphabet[next(&mut seed) as usize % alphabet.len()]),
+                )
+            };
+            let (haystack, needle) = (pick(haystack_len), pick(needle_len));
+            (haystack, needle, next(&mut seed))
+        })
+    }
+
+    #[test]
+    fn find_iter_mixed_ends() {
+        for (haystack, needle, mut seed) in cases(0x9e37_79b9_7f4a_7c15, b"ab") {
+            let expected = naive(&haystack, &needle, u8::eq);
+            check(find_iter(&haystack, &needle), &expected, &mut seed);
+        }
+    }
+
+    #[test]
+    fn find_iter_by_mixed_ends() {
+        let eq = |a: &u8, b: &u8| a.eq_ignore_ascii_case(b);
+        for (haystack, needle, mut seed) in cases(88_172_645_463_325_252, b"aAbB") {
+            let expected = naive(&haystack, &needle, eq);
+            check(find_iter_by(&haystack, &needle, eq), &expected, &mut seed);
+        }
+    }
+
+    #[test]
+    fn kmp_search_chars_matches_generic_path() {
+        // Mistura caracteres comuns, raros e fora do ASCII, para exercitar as escolhas do
+        // pré-filtro.
+        const ALPHABET: [char; 4] = ['e', 'q', 'é', '→'];
+        for (haystack, needle, _) in cases(0x2545_f491_4f6c_dd1d, &[0, 1, 2, 3]) {
+            let haystack = Vec::from_iter(haystack.iter().map(|&k| ALPHABET[k as usize]));
+            let needle = Vec::from_iter(needle.iter().map(|&k| ALPHABET[k as usize]));
+            assert_eq!(
+                kmp_search_chars(&haystack, &needle),
+                kmp_search(&haystack, &needle),
+                "haystack {haystack:?}, needle {needle:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn compiled_find_iter_mixed_ends() {
+        for failure_function in [FailureFunction::Lps, FailureFunction::Strong] {
+            for (haystack, needle, mut seed) in cases(0x2545_f491_4f6c_dd1d, b"aab") {
+                let kmp = Kmp::with_failure_function(&needle, failure_function);
+                let expected = naive(&haystack, &needle, u8::eq);
+                // Duas passadas pelo mesmo `Kmp`: a segunda já usa a tabela invertida
+                // guardada pela primeira.
+                check(kmp.find_iter(&haystack), &expected, &mut seed);
+                check(kmp.find_iter(&haystack), &expected, &mut seed);
+            }
+        }
+    }
 }
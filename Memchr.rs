@@ -0,0 +1,209 @@
+//! Busca vetorizada por um único byte, usada como pré-filtro pela busca em bytes.
+//!
+//! Enquanto nenhum prefixo do padrão casou (`j == 0`), o laço do KMP só avança no texto
+//! um byte por vez. Nesse trecho, basta procurar o próximo lugar onde um byte raro do
+//! padrão aparece, o que dá para fazer comparando 16 ou 32 bytes por instrução. Em
+//! x86_64, usamos AVX2 quando o processador tem (detectado em tempo de execução, com a
+//! feature `std`) e SSE2 caso contrário (faz parte da arquitetura base); nas demais, uma
+//! versão portável.
+
+/// Retorna o índice da primeira ocorrência de `byte` em `haystack`, se houver.
+pub(crate) fn memchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // Sem a `std`, não há detecção em tempo de execução: só usamos AVX2 se o alvo da
+        // compilação já garante que ele existe.
+        #[cfg(feature = "std")]
+        let avx2 = std::is_x86_feature_detected!("avx2");
+        #[cfg(not(feature = "std"))]
+        let avx2 = cfg!(target_feature = "avx2");
+
+        if avx2 {
+            // SAFETY: acabamos de verificar que o processador suporta AVX2.
+            return unsafe { x86::memchr_avx2(byte, haystack) };
+        }
+        // SAFETY: SSE2 faz parte da base do x86_64 e está sempre disponível.
+        unsafe { x86::memchr_sse2(byte, haystack) }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        memchr_fallback(byte, haystack)
+    }
+}
+
+/// Versão portável, byte a byte, usada nas demais arquiteturas e para o resto que não
+/// preenche um vetor inteiro.
+fn memchr_fallback(byte: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == byte)
+}
+
+/// Escolhe o byte do padrão a ser usado no pré-filtro: o mais raro segundo
+/// [`byte_rank`], retornando a sua posição no padrão e o próprio byte. Em caso de empate,
+/// fica o primeiro. `needle` não pode ser vazio.
+pub(crate) fn rare_byte(needle: &[u8]) -> (usize, u8) {
+    let (offset, &byte) = needle
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &byte)| byte_rank(byte))
+        .expect("o padrão não pode ser vazio");
+    (offset, byte)
+}
+
+/// Como [`rare_byte`], para um padrão de `char`s. Caracteres fora do ASCII são tratados
+/// como raros.
+pub(crate) fn rare_char(needle: &[char]) -> (usize, char) {
+    let (offset, &ch) = needle
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &ch)| u8::try_from(ch).map_or(0, byte_rank))
+        .expect("o padrão não pode ser vazio");
+    (offset, ch)
+}
+
+/// Frequência aproximada de um byte em textos e dados binários típicos: quanto maior, mais
+/// comum. É só uma heurística; qualquer escolha dá o mesmo resultado, muda só a velocidade.
+fn byte_rank(byte: u8) -> usize {
+    // Do mais comum para o menos comum. Bytes fora da lista (pontuação, dígitos, bytes
+    // altos) são tratados como raros.
+    const COMMON: &[u8] = b" \0etaoinsrhldcumfpgwybvkxjqz\n";
+
+    COMMON
+        .iter()
+        .position(|&common| common == byte.to_ascii_lowercase())
+        .map_or(0, |index| COMMON.len() - index)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::memchr_fallback;
+    use core::arch::x86_64::*;
+
+    /// # Safety
+    ///
+    /// O processador precisa suportar SSE2.
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn memchr_sse2(byte: u8, haystack: &[u8]) -> Option<usize> {
+        const WIDTH: usize = 16;
+        let target = _mm_set1_epi8(byte as i8);
+
+        let mut offset = 0;
+        while offset + WIDTH <= haystack.len() {
+            // SAFETY: `offset + WIDTH <= haystack.len()`, então os 16 bytes lidos estão
+            // dentro da fatia; `loadu` não exige alinhamento.
+            let chunk = unsafe { _mm_loadu_si128(haystack.as_ptr().add(offset).cast()) };
+            let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(chunk, target)) as u32;
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += WIDTH;
+        }
+
+        memchr_fallback(byte, &haystack[offset..]).map(|k| offset + k)
+    }
+
+    /// # Safety
+    ///
+    /// O processador precisa suportar AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn memchr_avx2(byte: u8, haystack: &[u8]) -> Option<usize> {
+        const WIDTH: usize = 32;
+        let target = _mm256_set1_epi8(byte as i8);
+
+        let mut offset = 0;
+        while offset + WIDTH <= haystack.len() {
+            // SAFETY: `offset + WIDTH <= haystack.len()`, então os 32 bytes lidos estão
+            // dentro da fatia; `loadu` não exige alinhamento.
+            let chunk = unsafe { _mm256_loadu_si256(haystack.as_ptr().add(offset).cast()) };
+            let mask = _mm256_movemask_epi8(_mm256_cmpeq_epi8(chunk, target)) as u32;
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += WIDTH;
+        }
+
+        // O resto (menos de 32 bytes) ainda aproveita os vetores de 16 bytes.
+        // SAFETY: todo processador com AVX2 também tem SSE2.
+        let rest = unsafe { memchr_sse2(byte, &haystack[offset..]) };
+        rest.map(|k| offset + k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{memchr, memchr_fallback};
+    use crate::{kmp_search, kmp_search_bytes};
+    use alloc::vec::Vec;
+
+    /// Gerador xorshift, para ter entradas "aleatórias" reprodutíveis sem dependências.
+    fn next(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    /// Confere `memchr` e, em x86_64, também a versão SSE2 diretamente (`memchr` escolhe
+    /// AVX2 sempre que o processador tem) contra a versão portável.
+    fn check(byte: u8, haystack: &[u8]) -> Option<usize> {
+        let expected = memchr_fallback(byte, haystack);
+        assert_eq!(memchr(byte, haystack), expected, "{haystack:?}");
+        #[cfg(target_arch = "x86_64")]
+        {
+            // SAFETY: SSE2 faz parte da base do x86_64.
+            let sse2 = unsafe { super::x86::memchr_sse2(byte, haystack) };
+            assert_eq!(sse2, expected, "{haystack:?}");
+        }
+        expected
+    }
+
+    #[test]
+    fn memchr_matches_fallback_at_every_offset() {
+        // Até 70 bytes cobre um vetor AVX2 (32), um SSE2 (16) e o resto byte a byte, em
+        // todas as combinações.
+        for len in 0..=70 {
+            let mut haystack = Vec::from_iter((0..len).map(|k| b'a' + (k % 7) as u8));
+            assert_eq!(check(b'z', &haystack), None);
+
+            for offset in 0..len {
+                haystack[offset] = b'z';
+                assert_eq!(check(b'z', &haystack), Some(offset));
+                // Uma segunda ocorrência depois da primeira não muda o resultado.
+                if offset + 1 < len {
+                    haystack[len - 1] = b'z';
+                    assert_eq!(check(b'z', &haystack), Some(offset));
+                    haystack[len - 1] = b'a' + ((len - 1) % 7) as u8;
+                }
+                haystack[offset] = b'a' + (offset % 7) as u8;
+            }
+        }
+    }
+
+    #[test]
+    fn memchr_matches_fallback_on_random_input() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..2000 {
+            let len = (next(&mut seed) % 100) as usize;
+            let haystack = Vec::from_iter((0..len).map(|_| next(&mut seed) as u8 % 8));
+            let byte = next(&mut seed) as u8 % 9;
+            check(byte, &haystack);
+        }
+    }
+
+    #[test]
+    fn kmp_search_bytes_matches_generic_path() {
+        let mut seed = 88_172_645_463_325_252;
+        for _ in 0..5000 {
+            let haystack_len = (next(&mut seed) % 200) as usize;
+            let needle_len = (next(&mut seed) % 6) as usize;
+            let haystack =
+                Vec::from_iter((0..haystack_len).map(|_| b'a' + next(&mut seed) as u8 % 3));
+            let needle = Vec::from_iter((0..needle_len).map(|_| b'a' + next(&mut seed) as u8 % 3));
+            assert_eq!(
+                kmp_search_bytes(&haystack, &needle),
+                kmp_search(&haystack, &needle),
+                "haystack {haystack:?}, needle {needle:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! O algoritmo Two-Way de Crochemore e Perrin.
+//!
+//! Assim como o KMP, o Two-Way roda em tempo linear no pior caso, mas usa apenas O(1) de
+//! memória extra: em vez de uma tabela do tamanho do padrão, ele guarda só uma "posição
+//! crítica" e um período, calculados a partir de uma fatoração do padrão em duas metades
+//! (daí o nome). A metade direita é comparada da esquerda para a direita e, se casar, a
+//! metade esquerda é comparada da direita para a esquerda.
+//!
+//! A fatoração depende de uma ordem total sobre os elementos, por isso aqui é exigido
+//! `T: Ord` (qualquer ordem consistente com a igualdade serve).
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+
+/// Encontra todas as ocorrências de `needle` em `haystack` com o algoritmo Two-Way.
+///
+/// Segue o mesmo contrato de `kmp_search`: retorna os índices de início de todas as
+/// ocorrências, inclusive sobrepostas, em ordem crescente, e um padrão vazio (ou maior que
+/// o texto) não tem ocorrências. Fora o vetor de resultados, nada é alocado; para uma
+/// busca sem nenhuma alocação, use [`two_way_find_iter`] (disponível também sem a
+/// feature `alloc`).
+#[cfg(feature = "alloc")]
+pub fn two_way_search<T>(haystack: &[T], needle: &[T]) -> Vec<usize>
+where
+    T: Ord,
+{
+    two_way_find_iter(haystack, needle).collect()
+}
+
+/// Versão preguiçosa de `two_way_search`, que não aloca nada.
+pub fn two_way_find_iter<'h, 'n, T>(haystack: &'h [T], needle: &'n [T]) -> TwoWayIter<'h, 'n, T>
+where
+    T: Ord,
+{
+    let (critical_pos, period, long_period) = if needle.is_empty() {
+        (0, 1, true)
+    } else {
+        critical_factorization(needle)
+    };
+
+    TwoWayIter {
+        haystack,
+        needle,
+        critical_pos,
+        period,
+        long_period,
+        position: 0,
+        memory: 0,
+    }
+}
+
+/// Iterador sobre as ocorrências de um padrão, criado por [`two_way_find_iter`].
+#[derive(Debug, Clone)]
+pub struct TwoWayIter<'h, 'n, T> {
+    haystack: &'h [T],
+    needle: &'n [T],
+    critical_pos: usize, // início da metade direita do padrão
+    period: usize,       // período do padrão (ou um limite inferior, se `long_period`)
+    long_period: bool,   // o período é longo demais para valer a pena lembrar prefixos
+    position: usize,     // alinhamento atual do padrão no texto
+    memory: usize,       // prefixo do padrão que já sabemos casar no alinhamento atual
+}
+
+impl<T> Iterator for TwoWayIter<'_, '_, T>
+where
+    T: Ord,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let (haystack, needle) = (self.haystack, self.needle);
+        let m = needle.len();
+        if m == 0 {
+            return None;
+        }
+
+        'search: while self.position + m <= haystack.len() {
+            let window = &haystack[self.position..self.position + m];
+
+            // 1. Metade direita, da esquerda para a direita. No caso periódico, o trecho
+            // `..memory` já foi verificado no alinhamento anterior.
+            let start = if self.long_period {
+                self.critical_pos
+            } else {
+                self.critical_pos.max(self.memory)
+            };
+            for i in start..m {
+                if needle[i] != window[i] {
+                    // Salta para alinhar o elemento divergente com o início da metade direita.
+                    self.position += i - self.critical_pos + 1;
+                    self.memory = 0;
+                    continue 'search;
+                }
+            }
+
+            // 2. Metade esquerda, da direita para a esquerda.
+            let end = if self.long_period { 0 } else { self.memory };
+            for i in (end..self.critical_pos).rev() {
+                if needle[i] != window[i] {
+                    // Salta um período inteiro; no caso periódico, o prefixo de tamanho
+                    // `m - period` do padrão continua casando no novo alinhamento.
+                    self.position += self.period;
+                    if !self.long_period {
+                        self.memory = m - self.period;
+                    }
+                    continue 'search;
+                }
+            }
+
+            // 3. Ocorrência completa. Avançar apenas um período preserva as ocorrências
+            // sobrepostas (nenhuma ocorrência pode começar antes disso).
+            let found = self.position;
+            self.position += self.period;
+            if !self.long_period {
+                self.memory = m - self.period;
+            }
+            return Some(found);
+        }
+
+        None
+    }
+}
+
+impl<T> FusedIterator for TwoWayIter<'_, '_, T> where T: Ord {}
+
+/// Calcula a fatoração crítica do padrão: retorna a posição crítica, o período usado nos
+/// saltos, e se o padrão deve ser tratado como de "período longo".
+fn critical_factorization<T>(needle: &[T]) -> (usize, usize, bool)
+where
+    T: Ord,
+{
+    // A posição crítica é o maior dos inícios dos sufixos máximos segundo a ordem e
+    // segundo a ordem inversa.
+    let (pos_less, period_less) = maximal_suffix(needle, Ordering::Less);
+    let (pos_greater, period_greater) = maximal_suffix(needle, Ordering::Greater);
+    let (critical_pos, period) = if pos_less > pos_greater {
+        (pos_less, period_less)
+    } else {
+        (pos_greater, period_greater)
+    };
+
+    if needle[..critical_pos] == needle[period..period + critical_pos] {
+        // O padrão é periódico: `period` é o seu período de fato.
+        (critical_pos, period, false)
+    } else {
+        // Caso contrário, o período do padrão é maior que este limite, que ainda é um
+        // salto seguro e dispensa a memória do prefixo já casado.
+        let period = critical_pos.max(needle.len() - critical_pos) + 1;
+        (critical_pos, period, true)
+    }
+}
+
+/// Encontra o início do sufixo máximo de `needle` e o período desse sufixo. `order`
+/// escolhe a ordem: com `Ordering::Less`, um elemento menor encerra o candidato atual;
+/// com `Ordering::Greater`, a ordem é invertida.
+fn maximal_suffix<T>(needle: &[T], order: Ordering) -> (usize, usize)
+where
+    T: Ord,
+{
+    let mut left = 0; // início do melhor sufixo até agora (`i` no artigo)
+    let mut right = 1; // início do sufixo sendo comparado (`j` no artigo)
+    let mut offset = 0; // quantos elementos dos dois sufixos já casaram (`k - 1`)
+    let mut period = 1; // período do melhor sufixo (`p`)
+
+    while right + offset < needle.len() {
+        let a = &needle[right + offset];
+        let b = &needle[left + offset];
+        let ordering = a.cmp(b);
+        if ordering == order {
+            // O sufixo em `right` é menor: o período passa a ser todo o trecho até aqui.
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if ordering == Ordering::Equal {
+            // Avança pela repetição do período atual.
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // O sufixo em `right` é maior: ele vira o novo candidato.
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+
+    (left, period)
+}
@@ -0,0 +1,137 @@
+//! O algoritmo Z e conversões entre o array Z e a tabela LPS do KMP.
+//!
+//! O array Z de uma sequência `s` guarda, para cada posição `i`, o comprimento do maior
+//! prefixo comum entre `s` e o sufixo `s[i..]`. Ele carrega a mesma informação que a
+//! tabela LPS (as bordas de todos os prefixos), só que indexada pelo início da
+//! repetição, e não pelo seu fim; por isso é possível converter um no outro.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Calcula o array Z de `s` em tempo linear.
+///
+/// `z[i]` é o comprimento do maior prefixo comum entre `s` e `s[i..]`. Por convenção,
+/// `z[0] == s.len()` (a sequência inteira é prefixo de si mesma).
+pub fn z_array<T>(s: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    z_array_by_index(s.len(), |a, b| s[a] == s[b])
+}
+
+/// Encontra todas as ocorrências de `needle` em `haystack` usando o array Z de
+/// `needle + separador + haystack`.
+///
+/// O separador é apenas uma posição virtual que não é igual a nada, inclusive a outros
+/// elementos de `T`; por isso não é preciso escolher um valor sentinela que não apareça
+/// nos dados, e nenhuma concatenação é de fato alocada. Segue o mesmo contrato de
+/// `kmp_search`: todas as ocorrências, inclusive sobrepostas, em ordem crescente.
+pub fn z_search<T>(haystack: &[T], needle: &[T]) -> Vec<usize>
+where
+    T: PartialEq,
+{
+    let m = needle.len();
+    if m == 0 || haystack.len() < m {
+        return vec![];
+    }
+
+    // Posições da sequência virtual: `0..m` é o needle, `m` é o separador e o restante
+    // é o haystack.
+    let element = |index: usize| match index.cmp(&m) {
+        Ordering::Less => Some(&needle[index]),
+        Ordering::Equal => None,
+        Ordering::Greater => Some(&haystack[index - m - 1]),
+    };
+    let z = z_array_by_index(m + 1 + haystack.len(), |a, b| {
+        match (element(a), element(b)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    });
+
+    // Graças ao separador, nenhum valor passa de `m`: `z[i] == m` é uma ocorrência.
+    z[m + 1..]
+        .iter()
+        .enumerate()
+        .filter(|&(_, &length)| length == m)
+        .map(|(start, _)| start)
+        .collect()
+}
+
+/// Converte um array Z na tabela LPS equivalente (a mesma que [`crate::compute_lps_table`]
+/// produziria para a sequência original).
+pub fn z_to_lps(z: &[usize]) -> Vec<usize> {
+    let mut lps = vec![0; z.len()];
+
+    // Cada caixa `[i, i + z[i])` é uma borda para todos os prefixos que terminam dentro
+    // dela. Percorrendo as caixas da esquerda para a direita, a primeira que cobre uma
+    // posição é a que dá a borda mais longa; as posições já preenchidas são puladas, o
+    // que mantém o total linear.
+    for i in 1..z.len() {
+        for j in (0..z[i]).rev() {
+            if lps[i + j] > 0 {
+                break;
+            }
+            lps[i + j] = j + 1;
+        }
+    }
+
+    lps
+}
+
+/// Converte uma tabela LPS (como a de [`crate::compute_lps_table`]) no array Z equivalente.
+///
+/// A tabela LPS determina quais posições da sequência original são iguais entre si: uma
+/// borda em `i` diz que `s[i] == s[lps[i] - 1]`, e a ausência de borda diz que `s[i]` não
+/// estende nenhuma. Reconstruímos uma sequência com exatamente essas igualdades (um novo
+/// símbolo para cada posição sem borda) e calculamos o seu array Z.
+pub fn lps_to_z(lps: &[usize]) -> Vec<usize> {
+    let mut symbols = Vec::with_capacity(lps.len());
+    let mut next_symbol = 0;
+    for (i, &border) in lps.iter().enumerate() {
+        if i > 0 && border > 0 {
+            symbols.push(symbols[border - 1]);
+        } else {
+            symbols.push(next_symbol);
+            next_symbol += 1;
+        }
+    }
+
+    z_array(&symbols)
+}
+
+/// Núcleo do algoritmo Z sobre uma sequência de tamanho `len`, acessada apenas por meio de
+/// `eq(a, b)`, que compara as posições `a` e `b`.
+fn z_array_by_index<F>(len: usize, eq: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut z = vec![0; len];
+    if len == 0 {
+        return z;
+    }
+    z[0] = len;
+
+    // `[left, right)` é a "caixa Z" que chega mais à direita: `s[left..right]` é igual ao
+    // prefixo `s[..right - left]`, então os valores dentro dela podem ser reaproveitados.
+    let (mut left, mut right) = (0, 0);
+    for i in 1..len {
+        let mut length = if i < right {
+            (right - i).min(z[i - left])
+        } else {
+            0
+        };
+        while i + length < len && eq(length, i + length) {
+            length += 1;
+        }
+
+        z[i] = length;
+        if i + length > right {
+            left = i;
+            right = i + length;
+        }
+    }
+
+    z
+}
@@ -0,0 +1,701 @@
+//! `hofalgs`: busca de padrões literais em arquivos ou na entrada padrão, no estilo do
+//! `grep`, usando os algoritmos da biblioteca.
+//!
+//! O formato estável da saída `--json` é descrito no próprio texto de ajuda (`USAGE`),
+//! que é onde os usuários do binário o encontram (`hofalgs --help`).
+
+#[path = "Demo.rs"]
+mod demo;
+
+use hofalgs::{boyer_moore, find_iter_bytes, two_way};
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::time::Instant;
+
+const USAGE: &str = "\
+Uso: hofalgs [OPÇÕES] PADRÃO [ARQUIVO...]
+     hofalgs demo
+
+Procura o PADRÃO (um texto literal) em cada ARQUIVO e imprime as linhas que o contêm,
+no formato `arquivo:linha:coluna:texto`. Sem arquivos, lê a entrada padrão (também
+indicada por `-`); com `-r`, busca no diretório atual.
+
+Opções:
+  -c, --count            imprime só a quantidade de linhas selecionadas por arquivo
+  -o, --only-matching    imprime só os trechos que casam, um por linha (não pode ser
+                         combinado com `-v`)
+  -v, --invert-match     seleciona as linhas que NÃO contêm o padrão
+  -r, --recursive        busca recursivamente nos diretórios
+      --json             saída em JSON Lines: um objeto por ocorrência e um resumo
+                         final (veja o formato abaixo)
+      --algorithm ALGO   algoritmo de busca: kmp (padrão), bm ou twoway
+  -h, --help             mostra esta ajuda
+      --                 encerra as opções (para buscar, por exemplo, `-v` ou `demo`)
+
+Subcomandos:
+  demo                   executa os exemplos de uso da biblioteca
+
+Código de saída: 0 se alguma linha foi selecionada, 1 se nenhuma, 2 em caso de erro.
+
+Saída JSON:
+  Com `--json`, a saída é JSON Lines: um objeto por linha, cada um com um campo `type`.
+  Este formato é estável; campos novos podem ser acrescentados, mas os existentes não
+  mudam de nome, tipo ou significado.
+
+  Para cada ocorrência (sem sobreposição, como em `-o`), na ordem em que aparecem:
+
+    {\"type\":\"match\",\"file\":\"src/a.txt\",\"offset\":42,\"line\":3,\"column\":7,\"text\":\"foo\"}
+
+    file      o caminho, como foi informado ou encontrado pelo `-r`; null para a
+              entrada padrão. Bytes que não são UTF-8 no caminho são trocados por
+              U+FFFD, então o nome pode não identificar o arquivo de forma exata
+    offset    deslocamento, em bytes, do início da ocorrência desde o início do arquivo
+    line      linha do início da ocorrência, a partir de 1
+    column    coluna (em bytes) do início da ocorrência, a partir de 1
+    text      o trecho que casou (sempre igual ao PADRÃO, que é UTF-8), mesmo quando o
+              resto do arquivo é binário
+
+  Ao final, sempre um único resumo:
+
+    {\"type\":\"summary\",\"files\":2,\"files_with_matches\":1,\"lines\":3,\"matches\":4,\"errors\":0,\"elapsed_ms\":1.234}
+
+    files               entradas lidas com sucesso
+    files_with_matches  quantas delas têm alguma ocorrência
+    lines, matches      total de linhas com ocorrências e de ocorrências
+    errors              entradas que não puderam ser lidas (os erros vão para a saída
+                        de erros)
+    elapsed_ms          tempo total da busca, em milissegundos
+
+  Com `-c`, só o resumo é emitido. `--json` não pode ser combinado com `-v`.";
+
+/// Nome usado nas mensagens e na saída para a entrada padrão.
+const STDIN_NAME: &str = "(entrada padrão)";
+
+/// O algoritmo usado na busca; todos produzem as mesmas ocorrências.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Algorithm {
+    #[default]
+    Kmp,
+    BoyerMoore,
+    TwoWay,
+}
+
+impl Algorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "kmp" => Some(Algorithm::Kmp),
+            "bm" => Some(Algorithm::BoyerMoore),
+            "twoway" => Some(Algorithm::TwoWay),
+            _ => None,
+        }
+    }
+
+    /// As ocorrências (inclusive sobrepostas) de `needle` em `haystack`, em ordem
+    /// crescente e sob demanda, sem juntá-las em um vetor.
+    fn find_iter<'a>(
+        self,
+        haystack: &'a [u8],
+        needle: &'a [u8],
+    ) -> Box<dyn Iterator<Item = usize> + 'a> {
+        match self {
+            Algorithm::Kmp => Box::new(find_iter_bytes(haystack, needle)),
+            Algorithm::BoyerMoore => Box::new(boyer_moore::bm_find_iter_bytes(haystack, needle)),
+            Algorithm::TwoWay => Box::new(two_way::two_way_find_iter(haystack, needle)),
+        }
+    }
+}
+
+/// As opções de uma busca, já validadas.
+#[derive(Debug, Default)]
+struct Options {
+    count: bool,
+    only_matching: bool,
+    invert: bool,
+    recursive: bool,
+    json: bool,
+    algorithm: Algorithm,
+    pattern: String,
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+enum Command {
+    Search(Options),
+    Demo,
+    Help,
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args_os().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("hofalgs: {}", message);
+            eprintln!("Use `hofalgs --help` para ver as opções.");
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Demo => match demo::run() {
+            Ok(()) => ExitCode::SUCCESS,
+            // Como na busca: a saída foi fechada, e não há mais o que fazer.
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("hofalgs: {}", error);
+                ExitCode::from(2)
+            }
+        },
+        Command::Search(options) => run(&options),
+    }
+}
+
+/// Interpreta os argumentos da linha de comando (sem o nome do programa).
+fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    // Só as opções e o PADRÃO precisam ser UTF-8; os caminhos ficam como `OsString`, para
+    // aceitar qualquer nome de arquivo do sistema.
+    let mut args = args.into_iter();
+    let mut options = Options::default();
+    let mut positional: Vec<OsString> = Vec::new();
+    let mut first = true;
+
+    while let Some(os_arg) = args.next() {
+        let Some(arg) = os_arg.to_str() else {
+            first = false;
+            positional.push(os_arg);
+            continue;
+        };
+        if first && arg == "demo" {
+            return match args.next() {
+                None => Ok(Command::Demo),
+                Some(_) => Err("o subcomando `demo` não aceita argumentos".to_string()),
+            };
+        }
+        first = false;
+
+        if arg == "--" {
+            positional.extend(args.by_ref());
+            break;
+        } else if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let is_flag = matches!(
+                name,
+                "count" | "only-matching" | "invert-match" | "recursive" | "json" | "help"
+            );
+            if is_flag && value.is_some() {
+                return Err(format!("a opção `--{}` não aceita valor", name));
+            }
+            match name {
+                "count" => options.count = true,
+                "only-matching" => options.only_matching = true,
+                "invert-match" => options.invert = true,
+                "recursive" => options.recursive = true,
+                "json" => options.json = true,
+                "help" => return Ok(Command::Help),
+                "algorithm" => {
+                    let value = match value {
+                        Some(value) => value,
+                        None => args
+                            .next()
+                            .ok_or("a opção `--algorithm` precisa de um valor")?
+                            .to_string_lossy()
+                            .into_owned(),
+                    };
+                    options.algorithm = Algorithm::from_name(&value).ok_or_else(|| {
+                        format!(
+                            "algoritmo desconhecido: `{}` (use kmp, bm ou twoway)",
+                            value
+                        )
+                    })?;
+                }
+                _ => return Err(format!("opção desconhecida: `--{}`", name)),
+            }
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            // Opções curtas podem vir agrupadas, como em `-rc`.
+            for flag in arg[1..].chars() {
+                match flag {
+                    'c' => options.count = true,
+                    'o' => options.only_matching = true,
+                    'v' => options.invert = true,
+                    'r' => options.recursive = true,
+                    'h' => return Ok(Command::Help),
+                    _ => return Err(format!("opção desconhecida: `-{}`", flag)),
+                }
+            }
+        } else {
+            positional.push(os_arg);
+        }
+    }
+
+    if options.json && options.invert {
+        return Err("`--json` não pode ser combinado com `-v`".to_string());
+    }
+    // Com `-v`, as linhas selecionadas não têm trechos que casam para o `-o` imprimir.
+    if options.only_matching && options.invert {
+        return Err("`-o` não pode ser combinado com `-v`".to_string());
+    }
+
+    let mut positional = positional.into_iter();
+    let pattern = positional
+        .next()
+        .ok_or("falta o PADRÃO")?
+        .into_string()
+        .map_err(|pattern| format!("o PADRÃO precisa ser UTF-8: {:?}", pattern))?;
+    if pattern.is_empty() {
+        return Err("o PADRÃO não pode ser vazio".to_string());
+    }
+    options.pattern = pattern;
+    options.paths = positional.map(PathBuf::from).collect();
+    Ok(Command::Search(options))
+}
+
+/// Quantas linhas foram selecionadas e quantas ocorrências (sem sobreposição) foram
+/// encontradas em uma entrada.
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    lines: usize,
+    matches: usize,
+}
+
+/// Os totais de uma execução, para o código de saída e o resumo do `--json`.
+#[derive(Debug, Default)]
+struct Summary {
+    files: usize,
+    files_with_matches: usize,
+    lines: usize,
+    matches: usize,
+    errors: usize,
+}
+
+impl Summary {
+    fn add(&mut self, counts: Counts) {
+        self.files += 1;
+        if counts.lines > 0 {
+            self.files_with_matches += 1;
+        }
+        self.lines += counts.lines;
+        self.matches += counts.matches;
+    }
+}
+
+/// Executa a busca em todas as entradas, imprimindo os resultados na saída padrão.
+fn run(options: &Options) -> ExitCode {
+    let started = Instant::now();
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut summary = Summary::default();
+
+    let mut report = |result: io::Result<Counts>, name: &str| match result {
+        Ok(counts) => summary.add(counts),
+        // A saída foi fechada (por exemplo, `hofalgs ... | head`), o que só acontece depois
+        // de alguma linha ter sido impressa; não há mais o que fazer.
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => process::exit(0),
+        Err(error) => {
+            eprintln!("hofalgs: {}: {}", name, error);
+            summary.errors += 1;
+        }
+    };
+
+    // `None` é a entrada padrão. Sem arquivos, lemos dela ou, com `-r`, do diretório atual.
+    let inputs: Vec<Option<PathBuf>> = if options.paths.is_empty() {
+        vec![options.recursive.then(|| PathBuf::from("."))]
+    } else {
+        options
+            .paths
+            .iter()
+            .map(|path| (path.as_os_str() != "-").then(|| path.clone()))
+            .collect()
+    };
+
+    for input in inputs {
+        match &input {
+            None => {
+                // Da entrada padrão, os resultados são impressos à medida que as linhas
+                // chegam, e não só no fim.
+                let result = search_lines(None, io::stdin().lock(), options, &mut out, true);
+                report(result, STDIN_NAME);
+            }
+            Some(path) if path.is_dir() => {
+                let result = if options.recursive {
+                    search_dir(path, options, &mut out, &mut report)
+                } else {
+                    Err(io::Error::other("é um diretório (use -r)"))
+                };
+                if let Err(error) = result {
+                    report(Err(error), &path.to_string_lossy());
+                }
+            }
+            Some(path) => report(
+                search_path(path, options, &mut out),
+                &path.to_string_lossy(),
+            ),
+        }
+    }
+
+    let mut result = Ok(());
+    if options.json {
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        result = writeln!(
+            out,
+            "{{\"type\":\"summary\",\"files\":{},\"files_with_matches\":{},\"lines\":{},\
+             \"matches\":{},\"errors\":{},\"elapsed_ms\":{:.3}}}",
+            summary.files,
+            summary.files_with_matches,
+            summary.lines,
+            summary.matches,
+            summary.errors,
+            elapsed_ms
+        );
+    }
+    if let Err(error) = result.and_then(|()| out.flush()) {
+        if error.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("hofalgs: {}", error);
+            summary.errors += 1;
+        }
+    }
+
+    if summary.errors > 0 {
+        ExitCode::from(2)
+    } else if summary.lines > 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// Busca em um único arquivo. Com a feature `mmap` (padrão), o arquivo é mapeado em
+/// memória e varrido de uma vez; caso contrário, ou se ele não puder ser mapeado, é lido
+/// em blocos de linhas. Em nenhum dos casos o arquivo inteiro é copiado para a memória.
+fn search_path<W>(path: &Path, options: &Options, out: &mut W) -> io::Result<Counts>
+where
+    W: Write,
+{
+    let name = path.to_string_lossy();
+    let file = File::open(path)?;
+
+    // SAFETY: como no `grep`, assumimos que os arquivos não são alterados durante a busca;
+    // se forem, os resultados podem ficar inconsistentes (veja `hofalgs::map_file`).
+    #[cfg(feature = "mmap")]
+    if let Some(map) = unsafe { hofalgs::map_file(&file)? } {
+        let mut search = LineSearch::new(Some(&name), options);
+        search.lines(out, &map, map.len(), 0)?;
+        return search.finish(out);
+    }
+
+    let reader = BufReader::with_capacity(READ_BUFFER_SIZE, file);
+    search_lines(Some(&name), reader, options, out, false)
+}
+
+/// Busca em todos os arquivos de um diretório e dos seus subdiretórios, em ordem
+/// alfabética. Como no `grep -r`, links simbólicos encontrados no caminho são ignorados
+/// (o que também evita ciclos). O resultado de cada arquivo, inclusive os erros, é
+/// passado para `report`, sem interromper a busca nos demais; só um erro ao listar o
+/// próprio `dir` é retornado.
+fn search_dir<W, R>(dir: &Path, options: &Options, out: &mut W, report: &mut R) -> io::Result<()>
+where
+    W: Write,
+    R: FnMut(io::Result<Counts>, &str),
+{
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (path, file_type) in entries {
+        if file_type.is_dir() {
+            if let Err(error) = search_dir(&path, options, out, report) {
+                report(Err(error), &path.to_string_lossy());
+            }
+        } else if file_type.is_file() {
+            report(search_path(&path, options, out), &path.to_string_lossy());
+        }
+    }
+
+    Ok(())
+}
+
+/// Tamanho do buffer de leitura dos arquivos que não são mapeados em memória.
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Busca em uma entrada lida aos poucos (`name` é `None` para a entrada padrão).
+///
+/// Só as linhas ainda não processadas ficam em memória. Uma ocorrência pode atravessar
+/// tantos `\n` quantos o padrão tiver, então as últimas linhas completas lidas só são
+/// processadas quando as seguintes chegam. Com `flush`, a saída é descarregada após cada
+/// bloco lido, para que os resultados apareçam enquanto a entrada ainda está chegando.
+fn search_lines<R, W>(
+    name: Option<&str>,
+    mut reader: R,
+    options: &Options,
+    out: &mut W,
+    flush: bool,
+) -> io::Result<Counts>
+where
+    R: BufRead,
+    W: Write,
+{
+    let lookahead = options
+        .pattern
+        .bytes()
+        .filter(|&byte| byte == b'\n')
+        .count();
+    let mut search = LineSearch::new(name, options);
+    let mut pending = Vec::new(); // linhas lidas e ainda não processadas
+    let mut line_ends = VecDeque::new(); // posição logo após cada `\n` em `pending`
+    let mut base = 0; // offset de `pending[0]` na entrada
+
+    loop {
+        let read = match reader.fill_buf() {
+            Ok(buffer) => {
+                let old_len = pending.len();
+                pending.extend_from_slice(buffer);
+                let new_ends = buffer
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &byte)| byte == b'\n');
+                line_ends.extend(new_ends.map(|(k, _)| old_len + k + 1));
+                buffer.len()
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        reader.consume(read);
+
+        // Processa tudo no fim da entrada; antes disso, só as linhas completas seguidas de
+        // pelo menos `lookahead` outras linhas completas.
+        let eof = read == 0;
+        let cut = if eof {
+            pending.len()
+        } else if line_ends.len() > lookahead {
+            line_ends[line_ends.len() - 1 - lookahead]
+        } else {
+            0
+        };
+
+        if cut > 0 {
+            search.lines(out, &pending, cut, base)?;
+            pending.drain(..cut);
+            base += cut;
+            while line_ends.front().is_some_and(|&end| end <= cut) {
+                line_ends.pop_front();
+            }
+            for end in &mut line_ends {
+                *end -= cut;
+            }
+            if flush {
+                out.flush()?;
+            }
+        }
+        if eof {
+            return search.finish(out);
+        }
+    }
+}
+
+/// O estado da busca em uma entrada, processada em um ou mais blocos de linhas
+/// completas (veja `LineSearch::lines`).
+struct LineSearch<'a> {
+    name: Option<&'a str>,
+    options: &'a Options,
+    // Fim (offset na entrada) da última ocorrência aceita. Vale para a entrada toda, e não
+    // por linha: uma ocorrência que atravessa um `\n` também impede as que se sobrepõem a
+    // ela na linha seguinte.
+    next_free: usize,
+    line_number: usize,
+    counts: Counts,
+}
+
+impl<'a> LineSearch<'a> {
+    fn new(name: Option<&'a str>, options: &'a Options) -> Self {
+        LineSearch {
+            name,
+            options,
+            next_free: 0,
+            line_number: 1,
+            counts: Counts::default(),
+        }
+    }
+
+    /// Processa as linhas de `block[..cut]` e imprime o resultado. `block` começa no
+    /// offset `base` da entrada, logo após um `\n` (ou no início), e `cut` é o fim de uma
+    /// linha (logo após o seu `\n`) ou o fim da entrada. O resto de `block` só é lido
+    /// pelas ocorrências que começam antes de `cut` e continuam depois dele.
+    fn lines<W>(&mut self, out: &mut W, block: &[u8], cut: usize, base: usize) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let pattern = self.options.pattern.as_bytes();
+        let mut starts = self.options.algorithm.find_iter(block, pattern).peekable();
+
+        let mut line_start = 0;
+        while line_start < cut {
+            let line_end = block[line_start..cut]
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map_or(cut, |k| line_start + k);
+
+            // As ocorrências que começam nesta linha (incluindo o `\n`, se o padrão começar
+            // por um), sem sobreposição, como no `grep -o`.
+            let mut line_matches = Vec::new();
+            while let Some(start) = starts.next_if(|&start| start <= line_end) {
+                if base + start >= self.next_free {
+                    line_matches.push(start - line_start);
+                    self.next_free = base + start + pattern.len();
+                }
+            }
+
+            let line = &block[line_start..line_end];
+            self.line(out, line, base + line_start, &line_matches)?;
+            line_start = line_end + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Conta e imprime uma linha, que começa no offset `offset` da entrada, com as colunas
+    /// (a partir de 0) das ocorrências aceitas nela.
+    fn line<W>(
+        &mut self,
+        out: &mut W,
+        line: &[u8],
+        offset: usize,
+        line_matches: &[usize],
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let options = self.options;
+        let display_name = self.name.unwrap_or(STDIN_NAME);
+        let line_number = self.line_number;
+        self.line_number += 1;
+        self.counts.matches += line_matches.len();
+        if line_matches.is_empty() != options.invert {
+            return Ok(());
+        }
+
+        self.counts.lines += 1;
+        if options.count {
+            // Só a contagem é impressa, no fim.
+        } else if options.invert {
+            writeln!(
+                out,
+                "{}:{}:{}",
+                display_name,
+                line_number,
+                String::from_utf8_lossy(line)
+            )?;
+        } else if options.json {
+            for &column in line_matches {
+                write_json_match(
+                    out,
+                    self.name,
+                    offset + column,
+                    line_number,
+                    column + 1,
+                    &options.pattern,
+                )?;
+            }
+        } else if options.only_matching {
+            // O trecho que casou é sempre o próprio padrão.
+            for &column in line_matches {
+                writeln!(
+                    out,
+                    "{}:{}:{}:{}",
+                    display_name,
+                    line_number,
+                    column + 1,
+                    options.pattern
+                )?;
+            }
+        } else {
+            writeln!(
+                out,
+                "{}:{}:{}:{}",
+                display_name,
+                line_number,
+                line_matches[0] + 1,
+                String::from_utf8_lossy(line)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Termina a busca, imprimindo a contagem com `-c`.
+    fn finish<W>(self, out: &mut W) -> io::Result<Counts>
+    where
+        W: Write,
+    {
+        if self.options.count && !self.options.json {
+            writeln!(
+                out,
+                "{}:{}",
+                self.name.unwrap_or(STDIN_NAME),
+                self.counts.lines
+            )?;
+        }
+        Ok(self.counts)
+    }
+}
+
+/// Escreve o objeto JSON de uma ocorrência (veja o formato em `USAGE`).
+///
+/// O trecho que casou é sempre o próprio padrão, que é UTF-8 (um PADRÃO que não é UTF-8 é
+/// rejeitado por `parse_args`); por isso ele é escrito direto como `text`.
+fn write_json_match<W>(
+    out: &mut W,
+    name: Option<&str>,
+    offset: usize,
+    line: usize,
+    column: usize,
+    text: &str,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    write!(out, "{{\"type\":\"match\",\"file\":")?;
+    match name {
+        Some(name) => write_json_string(out, name)?,
+        None => write!(out, "null")?,
+    }
+    write!(
+        out,
+        ",\"offset\":{},\"line\":{},\"column\":{},\"text\":",
+        offset, line, column
+    )?;
+    write_json_string(out, text)?;
+    writeln!(out, "}}")
+}
+
+/// Escreve `text` como uma string JSON, com aspas e os escapes necessários.
+fn write_json_string<W>(out: &mut W, text: &str) -> io::Result<()>
+where
+    W: Write,
+{
+    write!(out, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    write!(out, "\"")
+}